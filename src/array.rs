@@ -0,0 +1,116 @@
+use core::cell::UnsafeCell;
+use crate::{SinglytonRef, SinglytonRefMut, cell, lock::{BorrowLock, Lock}};
+
+/// A **thread-unsafe** global array of singletons, whose elements are borrowed independently of each other.
+///
+/// Each element has its own borrow tracking, so mutable references to different elements can be held at the same time. This makes it suitable for global pools, where each slot is handed out separately.
+///
+/// Using this across threads is undefined behaviour.
+///
+/// # Panics
+///
+/// In debug builds, usage of this abstraction is checked for safety at runtime.
+///
+/// * Using this struct across threads will panic, once a thread id provider has been set with `set_thread_id_provider`.
+/// * Mixing mutabilty of borrows of the same element will panic
+///
+/// Accessing an element out of bounds will always panic.
+pub struct SingletonArray<T, const N: usize> {
+	locks: [Lock; N],
+	values: UnsafeCell<[T; N]>
+}
+#[cfg(not(any(feature = "sync", feature = "spin")))]
+unsafe impl<T, const N: usize> Sync for SingletonArray<T, N> {}
+#[cfg(any(feature = "sync", feature = "spin"))]
+unsafe impl<T: Send + Sync, const N: usize> Sync for SingletonArray<T, N> {}
+
+impl<T, const N: usize> SingletonArray<T, N> {
+	#[inline]
+	pub const fn new(vals: [T; N]) -> Self {
+		Self {
+			locks: [Lock::INIT; N],
+			values: UnsafeCell::new(vals)
+		}
+	}
+
+	#[inline]
+	#[track_caller]
+	fn slot(&'static self, index: usize) -> (&'static Lock, *mut T) {
+		let lock = &self.locks[index];
+		(lock, unsafe { (self.values.get() as *mut T).add(index) })
+	}
+
+	#[inline]
+	#[track_caller]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires an **immutable reference** to the element at `index`.
+	///
+	/// Panics if `index` is out of bounds.
+	///
+	/// In debug builds, this will panic if the element is mutably accessed from a different thread or if a mutable reference to it is currently held.
+	pub fn get(&'static self, index: usize) -> SinglytonRef<'static, T> {
+		let (lock, value) = self.slot(index);
+		unsafe { cell::borrow(lock, None, value) }
+	}
+
+	#[inline]
+	#[track_caller]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **mutable reference** to the element at `index`.
+	///
+	/// Panics if `index` is out of bounds.
+	///
+	/// In debug builds, this will panic if the element is mutably accessed from a different thread or an existing mutable or immutable reference to it is currently held.
+	pub fn get_mut(&'static self, index: usize) -> SinglytonRefMut<'static, T> {
+		let (lock, value) = self.slot(index);
+		unsafe { cell::borrow_mut(lock, None, value) }
+	}
+
+	#[inline]
+	#[track_caller]
+	/// Acquires an **immutable reference** to the element at `index`, or returns `None` instead of panicking if a mutable reference to it is currently held.
+	///
+	/// Panics if `index` is out of bounds.
+	///
+	/// In release builds without the `sync` or `spin` features, this always returns `Some`.
+	pub fn try_get(&'static self, index: usize) -> Option<SinglytonRef<'static, T>> {
+		let (lock, value) = self.slot(index);
+		unsafe { cell::try_borrow(lock, value) }
+	}
+
+	#[inline]
+	#[track_caller]
+	/// Acquires a **mutable reference** to the element at `index`, or returns `None` instead of panicking if any other reference to it is currently held.
+	///
+	/// Panics if `index` is out of bounds.
+	///
+	/// In release builds without the `sync` or `spin` features, this always returns `Some`.
+	pub fn try_get_mut(&'static self, index: usize) -> Option<SinglytonRefMut<'static, T>> {
+		let (lock, value) = self.slot(index);
+		unsafe { cell::try_borrow_mut(lock, value) }
+	}
+
+	#[inline]
+	/// The number of elements in the array.
+	pub const fn len(&self) -> usize {
+		N
+	}
+
+	#[inline]
+	/// Tests if the array has no elements.
+	pub const fn is_empty(&self) -> bool {
+		N == 0
+	}
+}
+
+impl<T, const N: usize> SingletonArray<Option<T>, N> {
+	#[inline]
+	/// Returns an iterator over **immutable references** to the elements which are `Some`, such as the occupied slots of a global object pool.
+	///
+	/// Each element is only borrowed once it is reached, for as long as its guard is held.
+	///
+	/// In debug builds, iterating will panic if the array is mutably accessed from a different thread or if a mutable reference to an element is currently held.
+	pub fn iter_occupied(&'static self) -> impl Iterator<Item = SinglytonRef<'static, T>> {
+		(0..N).filter_map(move |index| SinglytonRef::try_map(self.get(index), |slot| slot.as_ref().ok_or(())).ok())
+	}
+}
\ No newline at end of file
@@ -0,0 +1,145 @@
+use core::{marker::PhantomData, ptr, sync::atomic::{AtomicPtr, Ordering}};
+use alloc::boxed::Box;
+
+struct Node<T> {
+	value: Box<T>,
+	// The value stored before this one, which is kept alive until it is reclaimed
+	prev: AtomicPtr<Node<T>>
+}
+
+/// A **thread-safe** global singleton for heap values which are rarely written and frequently read.
+///
+/// Unlike the other singletons in this crate, this is backed by an `AtomicPtr` and never hands out borrow guards, so loads are lock-free and can happen from any thread.
+///
+/// Because loaded references are `&'static`, storing a new value cannot drop the previous one. Instead, it is retired and kept alive alongside the new value so that existing references stay valid. Retired values are only dropped by `reclaim`, which must be called at a point where no references to them can still be held (such as once every reader has finished reloading). This makes this abstraction suited to globals that are written a handful of times (such as configuration that is swapped on reload), not ones that are written continuously.
+///
+/// Requires the `alloc` feature.
+pub struct SingletonAtomicBox<T> {
+	head: AtomicPtr<Node<T>>,
+	_marker: PhantomData<Box<T>>
+}
+unsafe impl<T: Send + Sync> Sync for SingletonAtomicBox<T> {}
+
+impl<T> SingletonAtomicBox<T> {
+	#[inline]
+	pub const fn new() -> Self {
+		Self {
+			head: AtomicPtr::new(ptr::null_mut()),
+			_marker: PhantomData
+		}
+	}
+
+	#[inline]
+	/// Loads a reference to the most recently stored value, or `None` if nothing has been stored yet.
+	pub fn load(&'static self) -> Option<&'static T> {
+		let head = self.head.load(Ordering::Acquire);
+		if head.is_null() {
+			None
+		} else {
+			Some(unsafe { &(*head).value })
+		}
+	}
+
+	/// Stores a new value in the singleton.
+	///
+	/// The previous value (if any) is retired rather than dropped, as other threads may still hold references to it. Use `reclaim` to drop retired values.
+	pub fn store(&'static self, val: Box<T>) {
+		let node = Box::into_raw(Box::new(Node {
+			value: val,
+			prev: AtomicPtr::new(ptr::null_mut())
+		}));
+
+		let mut prev = self.head.load(Ordering::Relaxed);
+		loop {
+			// The node is not visible to other threads until the exchange succeeds
+			unsafe { (*node).prev.store(prev, Ordering::Relaxed) };
+
+			match self.head.compare_exchange_weak(prev, node, Ordering::AcqRel, Ordering::Relaxed) {
+				Ok(_) => break,
+				Err(head) => prev = head
+			}
+		}
+	}
+
+	#[inline]
+	/// Tests if a value has been stored in the singleton.
+	pub fn is_some(&'static self) -> bool {
+		!self.head.load(Ordering::Acquire).is_null()
+	}
+
+	/// Drops every retired value, keeping only the value stored most recently.
+	///
+	/// # Safety
+	///
+	/// No references returned by `load` may still be alive, other than references to the value stored most recently, and this must not be called concurrently with `store` or `reclaim`.
+	pub unsafe fn reclaim(&'static self) {
+		let head = self.head.load(Ordering::Acquire);
+		if head.is_null() {
+			return;
+		}
+
+		let mut retired = (*head).prev.swap(ptr::null_mut(), Ordering::AcqRel);
+		while !retired.is_null() {
+			let node = Box::from_raw(retired);
+			retired = node.prev.load(Ordering::Relaxed);
+		}
+	}
+}
+
+impl<T> Default for SingletonAtomicBox<T> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+/// A **thread-safe** global optional singleton for a heap value which is set once, such as at startup, and then frequently read.
+///
+/// Like `SingletonAtomicBox`, this is backed by an `AtomicPtr` and never hands out borrow guards, so readers can cheaply poll whether it is set and read it from any thread without locking. As it can only be set once, the value is simply leaked, with no bookkeeping for previous values.
+///
+/// Requires the `alloc` feature.
+pub struct SingletonOptionAtomic<T> {
+	value: AtomicPtr<T>,
+	_marker: PhantomData<Box<T>>
+}
+unsafe impl<T: Send + Sync> Sync for SingletonOptionAtomic<T> {}
+
+impl<T> SingletonOptionAtomic<T> {
+	#[inline]
+	pub const fn new() -> Self {
+		Self {
+			value: AtomicPtr::new(ptr::null_mut()),
+			_marker: PhantomData
+		}
+	}
+
+	#[inline]
+	/// Loads a reference to the value, or `None` if it has not been set yet.
+	pub fn get(&'static self) -> Option<&'static T> {
+		unsafe { self.value.load(Ordering::Acquire).as_ref() }
+	}
+
+	#[inline]
+	/// Tests if the value has been set.
+	pub fn is_some(&'static self) -> bool {
+		!self.value.load(Ordering::Acquire).is_null()
+	}
+
+	/// Sets the value, if it has not been set yet.
+	///
+	/// Returns `Err(val)`, handing the value back, if it has already been set (including by another thread racing with this one).
+	pub fn set(&'static self, val: Box<T>) -> Result<(), Box<T>> {
+		let val = Box::into_raw(val);
+		match self.value.compare_exchange(ptr::null_mut(), val, Ordering::AcqRel, Ordering::Acquire) {
+			Ok(_) => Ok(()),
+			Err(_) => Err(unsafe { Box::from_raw(val) })
+		}
+	}
+}
+
+impl<T> Default for SingletonOptionAtomic<T> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
\ No newline at end of file
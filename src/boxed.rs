@@ -0,0 +1,96 @@
+use crate::{SinglytonCell, SinglytonRef, SinglytonRefMut, map_ref, map_ref_mut, violation::{self, ViolationKind}};
+use alloc::boxed::Box;
+
+/// A **thread-unsafe** global singleton which stores its value on the heap.
+///
+/// Large values stored in a `static` bloat the data or bss segments of the binary. This singleton only stores a pointer to a heap allocation made on initialization, so its own footprint stays small. In release builds without the `sync` or `spin` features, it is exactly pointer-sized.
+///
+/// Using this across threads is undefined behaviour.
+///
+/// Requires the `alloc` feature.
+///
+/// # Panics
+///
+/// In debug builds, usage of this abstraction is checked for safety at runtime.
+///
+/// * Using this struct across threads will panic, once a thread id provider has been set with `set_thread_id_provider`.
+/// * Mixing mutabilty of borrows will panic
+/// * Initializing the value more than once will panic.
+///
+/// Using this struct before initializing it will always panic.
+pub struct SingletonBoxed<T>(SinglytonCell<Option<Box<T>>>);
+
+impl<T> SingletonBoxed<T> {
+	#[inline]
+	pub const fn new() -> Self {
+		Self(SinglytonCell::new(None))
+	}
+
+	#[inline]
+	/// Initializes the singleton by moving `val` to the heap.
+	///
+	/// In debug builds, this will panic if the singleton is **already initialized**, it is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	pub fn init(&'static self, val: T) {
+		self.init_boxed(Box::new(val))
+	}
+
+	#[inline]
+	/// Initializes the singleton with a value which is already on the heap.
+	///
+	/// This avoids constructing very large values on the stack.
+	///
+	/// In debug builds, this will panic if the singleton is **already initialized**, it is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	pub fn init_boxed(&'static self, val: Box<T>) {
+		let mut boxed = self.0.get_mut();
+		#[cfg(debug_assertions)]
+		if boxed.is_some() {
+			violation::violation(ViolationKind::AlreadyInitialized, self.0.name());
+		}
+		*boxed = Some(val);
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires an **immutable reference** to the singleton.
+	///
+	/// Panics if the singleton is not initialized.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn get(&'static self) -> SinglytonRef<'static, T> {
+		let name = self.0.name();
+		map_ref(self.0.get(), |boxed| match boxed {
+			Some(val) => &**val,
+			None => violation::violation(ViolationKind::Uninitialized, name)
+		})
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **mutable reference** to the singleton.
+	///
+	/// Panics if the singleton is not initialized.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn get_mut(&'static self) -> SinglytonRefMut<'static, T> {
+		let name = self.0.name();
+		map_ref_mut(self.0.get_mut(), |boxed| match boxed {
+			Some(val) => &mut **val,
+			None => violation::violation(ViolationKind::Uninitialized, name)
+		})
+	}
+
+	#[inline]
+	/// Tests if the singleton has been initialized.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn is_initialized(&'static self) -> bool {
+		self.0.get().is_some()
+	}
+}
+
+impl<T> Default for SingletonBoxed<T> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
\ No newline at end of file
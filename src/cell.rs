@@ -1,180 +1,563 @@
-#[cfg(debug_assertions)]
-mod cell {
-	use atomic_refcell::{AtomicRefCell, AtomicRef, AtomicRefMut};
+use core::{ops::{Deref, DerefMut, Index, IndexMut}, fmt::{Debug, Display}, cell::UnsafeCell};
+use crate::lock::{BorrowLock, Lock};
 
-	pub type SinglytonRef<T> = AtomicRef<'static, T>;
-	pub type SinglytonRefMut<T> = AtomicRefMut<'static, T>;
+// Makes guards `!Send` (but still `Sync`), like `MutexGuard`, so that they cannot be held across an `.await` in a future which must be `Send`.
+// Guards must be released on the thread which acquired them, as the borrow belongs to that thread.
+#[derive(Clone, Copy, Default)]
+struct NotSend(core::marker::PhantomData<*const ()>);
+unsafe impl Sync for NotSend {}
+
+#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+/// An **immutable reference** to the contents of a singleton.
+///
+/// The borrow is released when this guard is dropped.
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// static SINGLETON: singlyton::Singleton<u32> = singlyton::Singleton::new(0);
+/// SINGLETON.get();
+/// ```
+#[cfg_attr(all(not(debug_assertions), not(any(feature = "sync", feature = "spin"))), repr(transparent))]
+pub struct SinglytonRef<'a, T: ?Sized> {
+	value: &'a T,
+	_guard: <Lock as BorrowLock>::Borrow<'a>,
+	_not_send: NotSend
+}
+impl<'a, T: ?Sized> Deref for SinglytonRef<'a, T> {
+	type Target = T;
 
 	#[inline]
-	pub fn map_ref<'a, T: ?Sized, U: ?Sized, F>(reference: AtomicRef<'a, T>, f: F) -> AtomicRef<'a, U>
+	fn deref(&self) -> &T {
+		self.value
+	}
+}
+impl<'a, T: ?Sized + Debug + 'a> Debug for SinglytonRef<'a, T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		self.value.fmt(f)
+	}
+}
+impl<'a, T: ?Sized + Display + 'a> Display for SinglytonRef<'a, T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		self.value.fmt(f)
+	}
+}
+impl<'a, T: ?Sized> SinglytonRef<'a, T> {
+	#[inline]
+	pub(crate) fn shorten<'b>(this: Self) -> SinglytonRef<'b, T>
 	where
-		F: FnOnce(&T) -> &U
+		'a: 'b
 	{
-		AtomicRef::map(reference, f)
+		// Every lock's guards are covariant over their lifetime, but this can't be expressed through the `BorrowLock` projection
+		unsafe { core::mem::transmute::<SinglytonRef<'a, T>, SinglytonRef<'b, T>>(this) }
 	}
 
 	#[inline]
-	pub fn map_ref_mut<'a, T: ?Sized, U: ?Sized, F>(reference: AtomicRefMut<'a, T>, f: F) -> AtomicRefMut<'a, U>
+	/// Projects this guard into a part of the singleton, like [`map_ref`], unless `f` fails.
+	///
+	/// If `f` returns an error, the original guard is handed back alongside it, so the singleton does not need to be borrowed again.
+	pub fn try_map<U: ?Sized, E, F>(this: Self, f: F) -> Result<SinglytonRef<'a, U>, (Self, E)>
 	where
-		F: FnOnce(&mut T) -> &mut U
+		F: FnOnce(&T) -> Result<&U, E>
 	{
-		AtomicRefMut::map(reference, f)
+		match f(this.value) {
+			Ok(value) => Ok(SinglytonRef { value, _guard: this._guard, _not_send: NotSend::default() }),
+			Err(err) => Err((this, err))
+		}
 	}
 
-	#[repr(transparent)]
-	pub(crate) struct SinglytonCell<T>(AtomicRefCell<T>);
+	#[inline]
+	/// Pins this guard, so that it can be passed to APIs which expect a `Pin<&T>`.
+	///
+	/// This requires `T: Unpin`, as most singletons let their value be moved out with a mutable reference (for example with `replace`), which would break the guarantees of `Pin` for `!Unpin` values. Use `SingletonLazy::get_pin` for those, which never moves its value once initialized.
+	pub fn into_pin(this: Self) -> core::pin::Pin<Self>
+	where
+		T: Unpin
+	{
+		core::pin::Pin::new(this)
+	}
 
-	impl<T> SinglytonCell<T> {
-		#[inline]
-		pub(crate) const fn new(val: T) -> SinglytonCell<T> {
-			SinglytonCell(AtomicRefCell::new(val))
-		}
+	#[inline]
+	/// Converts this guard into a plain reference which lives as long as the singleton, without ever releasing the borrow.
+	///
+	/// In debug builds, and with the `sync` or `spin` features, the singleton stays immutably borrowed forever, so any later attempt to mutably borrow it will panic or block.
+	pub fn leak(this: Self) -> &'a T {
+		core::mem::ManuallyDrop::new(this).value
+	}
+}
 
-		/*
-		#[inline]
-		pub(crate) fn map<U: ?Sized, F>(&'static self, f: F) -> SinglytonRef<U>
-		where
-			F: FnOnce(&T) -> &U
-		{
-			map_ref(self.get(), f)
-		}
+#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+/// A **mutable reference** to the contents of a singleton.
+///
+/// The borrow is released when this guard is dropped.
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// static SINGLETON: singlyton::Singleton<u32> = singlyton::Singleton::new(0);
+/// SINGLETON.get_mut();
+/// ```
+#[cfg_attr(all(not(debug_assertions), not(any(feature = "sync", feature = "spin", feature = "observers"))), repr(transparent))]
+pub struct SinglytonRefMut<'a, T: ?Sized> {
+	value: &'a mut T,
+	_guard: <Lock as BorrowLock>::BorrowMut<'a>,
+	_notify: Notify<'a>,
+	_not_send: NotSend
+}
+impl<'a, T: ?Sized> Deref for SinglytonRefMut<'a, T> {
+	type Target = T;
 
-		#[inline]
-		pub(crate) fn map_mut<U: ?Sized, F>(&'static self, f: F) -> SinglytonRefMut<U>
-		where
-			F: FnOnce(&mut T) -> &mut U
-		{
-			map_ref_mut(self.get_mut(), f)
-		}
-		*/
+	#[inline]
+	fn deref(&self) -> &T {
+		self.value
+	}
+}
+impl<'a, T: ?Sized> DerefMut for SinglytonRefMut<'a, T> {
+	#[inline]
+	fn deref_mut(&mut self) -> &mut T {
+		self.value
+	}
+}
+impl<'a, T: ?Sized + Debug + 'a> Debug for SinglytonRefMut<'a, T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		self.value.fmt(f)
+	}
+}
+impl<'a, T: ?Sized + Display + 'a> Display for SinglytonRefMut<'a, T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		self.value.fmt(f)
+	}
+}
+impl<'a, T: ?Sized> SinglytonRefMut<'a, T> {
+	#[inline]
+	pub(crate) fn shorten<'b>(this: Self) -> SinglytonRefMut<'b, T>
+	where
+		'a: 'b
+	{
+		// Every lock's guards are covariant over their lifetime, but this can't be expressed through the `BorrowLock` projection
+		unsafe { core::mem::transmute::<SinglytonRefMut<'a, T>, SinglytonRefMut<'b, T>>(this) }
+	}
 
-		#[inline]
-		pub(crate) fn get(&'static self) -> SinglytonRef<T> {
-			self.0.borrow()
-		}
+	#[inline]
+	/// Splits this guard into two **mutable references** to disjoint parts of the singleton, such as two fields of a struct.
+	///
+	/// The singleton stays mutably borrowed until both guards are dropped.
+	#[allow(clippy::unit_arg)]
+	pub fn map_split<U: ?Sized, V: ?Sized, F>(this: Self, f: F) -> (SinglytonRefMut<'a, U>, SinglytonRefMut<'a, V>)
+	where
+		F: FnOnce(&mut T) -> (&mut U, &mut V)
+	{
+		let (a, b) = f(this.value);
+		let (guard_a, guard_b) = Lock::split(this._guard);
+		(
+			SinglytonRefMut { value: a, _guard: guard_a, _notify: this._notify.clone(), _not_send: NotSend::default() },
+			SinglytonRefMut { value: b, _guard: guard_b, _notify: this._notify, _not_send: NotSend::default() }
+		)
+	}
 
-		#[inline]
-		pub(crate) fn get_mut(&'static self) -> SinglytonRefMut<T> {
-			self.0.borrow_mut()
-		}
+	#[inline]
+	/// Converts this guard into a plain mutable reference which lives as long as the singleton, without ever releasing the borrow.
+	///
+	/// In debug builds, and with the `sync` or `spin` features, the singleton stays mutably borrowed forever, so any later attempt to borrow it will panic or block. Observers of the singleton are never notified of changes made through the returned reference.
+	pub fn leak_mut(this: Self) -> &'a mut T {
+		let this = core::mem::ManuallyDrop::new(this);
+		// The rest of the guard is never dropped, so the singleton stays borrowed for the rest of its lifetime
+		unsafe { core::ptr::read(&this.value) }
+	}
 
-		#[inline]
-		pub(crate) unsafe fn get_unchecked(&'static self) -> &'static T {
-			&*self.0.as_ptr()
-		}
+	#[inline]
+	/// Returns an **immutable reference** to the value behind this guard, which can be passed to helpers while the guard is still held.
+	///
+	/// Acquiring another borrow of the singleton with `get` would panic in debug builds (or deadlock with the `sync` or `spin` features), as the singleton is already mutably borrowed by this guard.
+	pub fn reborrow(&self) -> &T {
+		self.value
+	}
 
-		#[inline]
-		pub(crate) unsafe fn get_mut_unchecked(&'static self) -> &'static mut T {
-			&mut *self.0.as_ptr()
-		}
+	#[inline]
+	/// Replaces the value behind this guard with `val`, returning the previous value.
+	///
+	/// This is useful for code which already holds the guard, as the singleton does not need to be borrowed again.
+	pub fn replace_inner(&mut self, val: T) -> T
+	where
+		T: Sized
+	{
+		core::mem::replace(self.value, val)
+	}
+
+	#[inline]
+	#[cfg(feature = "observers")]
+	pub(crate) fn notify(mut this: Self, notify: Notify<'a>) -> Self {
+		this._notify = notify;
+		this
 	}
 }
 
-#[cfg(not(debug_assertions))]
-mod cell {
-	use core::{ops::{Deref, DerefMut}, fmt::Debug, cell::UnsafeCell};
+#[inline]
+pub fn map_ref<'a, T: ?Sized, U: ?Sized, F>(reference: SinglytonRef<'a, T>, f: F) -> SinglytonRef<'a, U>
+where
+	F: FnOnce(&T) -> &U
+{
+	SinglytonRef {
+		value: f(reference.value),
+		_guard: reference._guard,
+		_not_send: NotSend::default()
+	}
+}
 
-	#[repr(transparent)]
-	pub struct SinglytonRef<'a, T: ?Sized>(&'a T);
-	impl<'a, T: ?Sized> Deref for SinglytonRef<'a, T> {
-		type Target = T;
+#[inline]
+pub fn map_ref_mut<'a, T: ?Sized, U: ?Sized, F>(reference: SinglytonRefMut<'a, T>, f: F) -> SinglytonRefMut<'a, U>
+where
+	F: FnOnce(&mut T) -> &mut U
+{
+	SinglytonRefMut {
+		value: f(reference.value),
+		_guard: reference._guard,
+		_notify: reference._notify,
+		_not_send: NotSend::default()
+	}
+}
 
-		#[inline]
-		fn deref(&self) -> &T {
-			self.0
-		}
+// The borrow must be acquired before the reference is created, which is why these build the guard before the value.
+// `value` must only ever be accessed through guards acquired from `lock`.
+
+#[inline]
+#[track_caller]
+pub(crate) unsafe fn borrow<'a, T: ?Sized>(lock: &'a Lock, name: Option<&'static str>, value: *const T) -> SinglytonRef<'a, T> {
+	SinglytonRef {
+		_guard: lock.borrow(name),
+		value: &*value,
+		_not_send: NotSend::default()
 	}
-	impl<'a, T: ?Sized + Debug + 'a> Debug for SinglytonRef<'a, T> {
-		fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-			self.0.fmt(f)
-		}
+}
+
+#[inline]
+#[track_caller]
+pub(crate) unsafe fn borrow_mut<'a, T: ?Sized>(lock: &'a Lock, name: Option<&'static str>, value: *mut T) -> SinglytonRefMut<'a, T> {
+	SinglytonRefMut {
+		_guard: lock.borrow_mut(name),
+		value: &mut *value,
+		_notify: Notify::default(),
+		_not_send: NotSend::default()
 	}
+}
 
-	#[repr(transparent)]
-	pub struct SinglytonRefMut<'a, T: ?Sized>(&'a mut T);
-	impl<'a, T: ?Sized> Deref for SinglytonRefMut<'a, T> {
-		type Target = T;
+#[inline]
+pub(crate) unsafe fn try_borrow<'a, T: ?Sized>(lock: &'a Lock, value: *const T) -> Option<SinglytonRef<'a, T>> {
+	lock.try_borrow().map(|guard| SinglytonRef {
+		_guard: guard,
+		value: &*value,
+		_not_send: NotSend::default()
+	})
+}
+
+#[inline]
+pub(crate) unsafe fn try_borrow_mut<'a, T: ?Sized>(lock: &'a Lock, value: *mut T) -> Option<SinglytonRefMut<'a, T>> {
+	lock.try_borrow_mut().map(|guard| SinglytonRefMut {
+		_guard: guard,
+		value: &mut *value,
+		_notify: Notify::default(),
+		_not_send: NotSend::default()
+	})
+}
 
-		#[inline]
-		fn deref(&self) -> &T {
-			self.0
+#[cfg_attr(all(not(debug_assertions), not(any(feature = "sync", feature = "spin"))), repr(transparent))]
+pub(crate) struct SinglytonCell<T> {
+	lock: Lock,
+	#[cfg(debug_assertions)]
+	name: Option<&'static str>,
+	value: UnsafeCell<T>
+}
+#[cfg(not(any(feature = "sync", feature = "spin")))]
+unsafe impl<T> Sync for SinglytonCell<T> {}
+#[cfg(any(feature = "sync", feature = "spin"))]
+unsafe impl<T: Send + Sync> Sync for SinglytonCell<T> {}
+
+impl<T> SinglytonCell<T> {
+	#[inline]
+	pub(crate) const fn new(val: T) -> SinglytonCell<T> {
+		SinglytonCell {
+			lock: Lock::INIT,
+			#[cfg(debug_assertions)]
+			name: None,
+			value: UnsafeCell::new(val)
 		}
 	}
-	impl<'a, T: ?Sized> DerefMut for SinglytonRefMut<'a, T> {
-		#[inline]
-		fn deref_mut(&mut self) -> &mut T {
-			self.0
+
+	#[inline]
+	pub(crate) const fn new_named(val: T, name: &'static str) -> SinglytonCell<T> {
+		#[cfg(not(debug_assertions))]
+		let _ = name;
+
+		SinglytonCell {
+			lock: Lock::INIT,
+			#[cfg(debug_assertions)]
+			name: Some(name),
+			value: UnsafeCell::new(val)
 		}
 	}
-	impl<'a, T: ?Sized + Debug + 'a> Debug for SinglytonRefMut<'a, T> {
-		fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
-			self.0.fmt(f)
+
+	#[inline]
+	/// The name of the singleton, which is only stored in debug builds.
+	pub(crate) fn name(&self) -> Option<&'static str> {
+		#[cfg(debug_assertions)] {
+			self.name
+		}
+		#[cfg(not(debug_assertions))] {
+			None
 		}
 	}
 
 	#[inline]
-	pub fn map_ref<'a, T: ?Sized, U: ?Sized, F>(reference: SinglytonRef<'a, T>, f: F) -> SinglytonRef<'a, U>
-	where
-		F: FnOnce(&T) -> &U
-	{
-		SinglytonRef(f(reference.0))
+	#[track_caller]
+	pub(crate) fn get(&self) -> SinglytonRef<'_, T> {
+		unsafe { borrow(&self.lock, self.name(), self.value.get()) }
 	}
 
 	#[inline]
-	pub fn map_ref_mut<'a, T: ?Sized, U: ?Sized, F>(reference: SinglytonRefMut<'a, T>, f: F) -> SinglytonRefMut<'a, U>
-	where
-		F: FnOnce(&mut T) -> &mut U
-	{
-		SinglytonRefMut(f(reference.0))
+	#[track_caller]
+	pub(crate) fn get_mut(&self) -> SinglytonRefMut<'_, T> {
+		unsafe { borrow_mut(&self.lock, self.name(), self.value.get()) }
 	}
 
-	#[repr(transparent)]
-	pub(crate) struct SinglytonCell<T>(UnsafeCell<T>);
+	#[inline]
+	#[track_caller]
+	#[allow(clippy::let_unit_value)]
+	pub(crate) fn get_upgradable(&self) -> (&T, <Lock as BorrowLock>::UpgradableBorrow<'_>) {
+		let guard = self.lock.borrow_upgradable(self.name());
+		(unsafe { &*self.value.get() }, guard)
+	}
 
-	impl<T> SinglytonCell<T> {
-		#[inline]
-		pub(crate) const fn new(val: T) -> SinglytonCell<T> {
-			SinglytonCell(UnsafeCell::new(val))
+	#[inline]
+	#[track_caller]
+	pub(crate) fn upgrade<'a>(&'a self, guard: <Lock as BorrowLock>::UpgradableBorrow<'a>) -> SinglytonRefMut<'a, T> {
+		SinglytonRefMut {
+			_guard: self.lock.upgrade(guard, self.name()),
+			value: unsafe { &mut *self.value.get() },
+			_notify: Notify::default(),
+			_not_send: NotSend::default()
 		}
+	}
 
-		/*
-		#[inline]
-		pub(crate) fn map<U: ?Sized, F>(&'static self, f: F) -> SinglytonRef<U>
-		where
-			F: FnOnce(&T) -> &U
-		{
-			map_ref(self.get(), f)
-		}
+	#[inline]
+	pub(crate) fn try_get(&self) -> Option<SinglytonRef<'_, T>> {
+		unsafe { try_borrow(&self.lock, self.value.get()) }
+	}
 
-		#[inline]
-		pub(crate) fn map_mut<U: ?Sized, F>(&'static self, f: F) -> SinglytonRefMut<U>
-		where
-			F: FnOnce(&mut T) -> &mut U
-		{
-			map_ref_mut(self.get_mut(), f)
-		}
-		*/
+	#[inline]
+	pub(crate) fn try_get_mut(&self) -> Option<SinglytonRefMut<'_, T>> {
+		unsafe { try_borrow_mut(&self.lock, self.value.get()) }
+	}
 
-		#[inline]
-		pub(crate) fn get(&self) -> SinglytonRef<'_, T> {
-			SinglytonRef(unsafe { &*self.0.get() })
-		}
+	#[inline]
+	pub(crate) fn immutable_borrow_count(&self) -> usize {
+		self.lock.immutable_borrow_count()
+	}
 
-		#[inline]
-		pub(crate) fn get_mut(&self) -> SinglytonRefMut<'_, T> {
-			SinglytonRefMut(unsafe { &mut *self.0.get() })
-		}
+	#[inline]
+	pub(crate) fn has_mutable_borrow(&self) -> bool {
+		self.lock.has_mutable_borrow()
+	}
+
+	#[inline]
+	#[cfg(feature = "test-util")]
+	pub(crate) fn reset_thread(&self) {
+		self.lock.reset_thread()
+	}
+
+	#[inline]
+	pub(crate) unsafe fn get_unchecked(&self) -> &T {
+		&*self.value.get()
+	}
+
+	#[inline]
+	#[allow(clippy::mut_from_ref)]
+	pub(crate) unsafe fn get_mut_unchecked(&self) -> &mut T {
+		&mut *self.value.get()
+	}
+
+	#[inline]
+	pub(crate) const unsafe fn peek(&self) -> &T {
+		&*self.value.get()
+	}
+
+	#[inline]
+	pub(crate) const fn as_raw(&self) -> *mut T {
+		self.value.get()
+	}
+}
 
-		#[inline]
-		pub(crate) unsafe fn get_unchecked(&self) -> SinglytonRef<'_, T> {
-			self.get()
+#[cfg(feature = "observers")]
+/// A singleton whose observers are notified after a **mutable reference** to it is dropped.
+pub(crate) trait Observed {
+	fn notify(&self);
+}
+
+#[cfg(feature = "observers")]
+struct NotifyOnDrop<'a>(&'a dyn Observed);
+// Notifying only accesses the singleton through its own checked borrows, just like the guard carrying it.
+#[cfg(feature = "observers")]
+unsafe impl Send for NotifyOnDrop<'_> {}
+#[cfg(feature = "observers")]
+unsafe impl Sync for NotifyOnDrop<'_> {}
+#[cfg(feature = "observers")]
+impl Drop for NotifyOnDrop<'_> {
+	#[inline]
+	fn drop(&mut self) {
+		self.0.notify();
+	}
+}
+
+// Carried by `SinglytonRefMut`s after their borrow guard, so that observers are notified once the borrow has been released.
+// Split `SinglytonRefMut`s share it, so observers are only notified once the last of them is dropped.
+#[cfg(feature = "observers")]
+#[derive(Clone, Default)]
+pub(crate) struct Notify<'a> {
+	_on_drop: Option<alloc::sync::Arc<NotifyOnDrop<'a>>>
+}
+#[cfg(feature = "observers")]
+impl<'a> Notify<'a> {
+	#[inline]
+	pub(crate) fn new(observed: &'a dyn Observed) -> Self {
+		Notify {
+			_on_drop: Some(alloc::sync::Arc::new(NotifyOnDrop(observed)))
 		}
+	}
+}
+
+#[cfg(not(feature = "observers"))]
+#[derive(Clone, Default)]
+pub(crate) struct Notify<'a>(core::marker::PhantomData<&'a ()>);
+
+impl<'a, T: ?Sized + AsRef<U>, U: ?Sized> AsRef<U> for SinglytonRef<'a, T> {
+	#[inline]
+	fn as_ref(&self) -> &U {
+		(**self).as_ref()
+	}
+}
+impl<'a, T: ?Sized + AsRef<U>, U: ?Sized> AsRef<U> for SinglytonRefMut<'a, T> {
+	#[inline]
+	fn as_ref(&self) -> &U {
+		(**self).as_ref()
+	}
+}
+impl<'a, T: ?Sized + AsMut<U>, U: ?Sized> AsMut<U> for SinglytonRefMut<'a, T> {
+	#[inline]
+	fn as_mut(&mut self) -> &mut U {
+		(**self).as_mut()
+	}
+}
+
+impl<'a, T: ?Sized + Index<I>, I> Index<I> for SinglytonRef<'a, T> {
+	type Output = T::Output;
+
+	#[inline]
+	fn index(&self, index: I) -> &T::Output {
+		&(**self)[index]
+	}
+}
+impl<'a, T: ?Sized + Index<I>, I> Index<I> for SinglytonRefMut<'a, T> {
+	type Output = T::Output;
+
+	#[inline]
+	fn index(&self, index: I) -> &T::Output {
+		&(**self)[index]
+	}
+}
+impl<'a, T: ?Sized + IndexMut<I>, I> IndexMut<I> for SinglytonRefMut<'a, T> {
+	#[inline]
+	fn index_mut(&mut self, index: I) -> &mut T::Output {
+		&mut (**self)[index]
+	}
+}
+
+impl<'a, 'b, T: ?Sized> IntoIterator for &'a SinglytonRef<'b, T>
+where
+	&'a T: IntoIterator
+{
+	type Item = <&'a T as IntoIterator>::Item;
+	type IntoIter = <&'a T as IntoIterator>::IntoIter;
+
+	#[inline]
+	fn into_iter(self) -> Self::IntoIter {
+		(**self).into_iter()
+	}
+}
+impl<'a, 'b, T: ?Sized> IntoIterator for &'a SinglytonRefMut<'b, T>
+where
+	&'a T: IntoIterator
+{
+	type Item = <&'a T as IntoIterator>::Item;
+	type IntoIter = <&'a T as IntoIterator>::IntoIter;
+
+	#[inline]
+	fn into_iter(self) -> Self::IntoIter {
+		(**self).into_iter()
+	}
+}
+impl<'a, 'b, T: ?Sized> IntoIterator for &'a mut SinglytonRefMut<'b, T>
+where
+	&'a mut T: IntoIterator
+{
+	type Item = <&'a mut T as IntoIterator>::Item;
+	type IntoIter = <&'a mut T as IntoIterator>::IntoIter;
 
-		#[inline]
-		pub(crate) unsafe fn get_mut_unchecked(&self) -> SinglytonRefMut<'_, T> {
-			self.get_mut()
+	#[inline]
+	fn into_iter(self) -> Self::IntoIter {
+		(**self).into_iter()
+	}
+}
+
+#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+/// An **immutable reference** to the contents of a singleton which can later be upgraded to a **mutable reference**.
+///
+/// The borrow is released when this guard is dropped. Upgrading it never releases the borrow, so nothing can change the value between it being inspected and it being mutated.
+///
+/// With the `sync` or `spin` features, this holds the singleton exclusively from the start, as other readers could otherwise prevent the upgrade forever.
+pub struct SinglytonUpgradableRef<'a, T> {
+	singleton: &'a crate::Singleton<T>,
+	value: &'a T,
+	_guard: <Lock as BorrowLock>::UpgradableBorrow<'a>,
+	_not_send: NotSend
+}
+impl<T> SinglytonUpgradableRef<'static, T> {
+	#[inline]
+	#[track_caller]
+	pub(crate) fn new(singleton: &'static crate::Singleton<T>) -> Self {
+		let (value, guard) = singleton.0.get_upgradable();
+		Self {
+			singleton,
+			value,
+			_guard: guard,
+			_not_send: NotSend::default()
 		}
 	}
+
+	#[inline]
+	#[track_caller]
+	/// Upgrades this guard to a **mutable reference**, without releasing the borrow in between.
+	///
+	/// Observers of the singleton are notified once the returned reference is dropped, as with `Singleton::get_mut`.
+	///
+	/// In debug builds, this will panic if any other mutable or immutable reference is currently held.
+	#[allow(clippy::unit_arg)]
+	pub fn upgrade(this: Self) -> SinglytonRefMut<'static, T> {
+		let singleton = this.singleton;
+		singleton.observe(singleton.0.upgrade(this._guard))
+	}
 }
+impl<'a, T> Deref for SinglytonUpgradableRef<'a, T> {
+	type Target = T;
 
-pub use cell::*;
\ No newline at end of file
+	#[inline]
+	fn deref(&self) -> &T {
+		self.value
+	}
+}
+impl<'a, T: Debug + 'a> Debug for SinglytonUpgradableRef<'a, T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		self.value.fmt(f)
+	}
+}
+impl<'a, T: Display + 'a> Display for SinglytonUpgradableRef<'a, T> {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		self.value.fmt(f)
+	}
+}
\ No newline at end of file
@@ -0,0 +1,86 @@
+use crate::{SingletonUninit, SinglytonRef, SinglytonRefMut, violation::{self, ViolationKind}};
+
+/// A **thread-unsafe** global singleton which is set exactly once at startup, and is always initialized afterwards.
+///
+/// This is for globals which need a value computed at runtime, such as from command line arguments, but which are otherwise used like a `Singleton`. Unlike `SingletonUninit`, accessing it before it is set is always checked, in release builds too. Unlike `SingletonLazy`, the value is supplied by the caller rather than by an initializer.
+///
+/// Using this across threads is undefined behaviour.
+///
+/// # Panics
+///
+/// In debug builds, usage of this abstraction is checked for safety at runtime.
+///
+/// * Using this struct across threads will panic, once a thread id provider has been set with `set_thread_id_provider`.
+/// * Mixing mutabilty of borrows will panic
+///
+/// Accessing the singleton before it is set, or setting it more than once, will always panic.
+pub struct DeferredSingleton<T>(SingletonUninit<T>);
+
+impl<T> Default for DeferredSingleton<T> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl<T> DeferredSingleton<T> {
+	#[inline]
+	pub const fn new() -> Self {
+		Self(SingletonUninit::uninit())
+	}
+
+	#[cold]
+	#[inline(never)]
+	#[track_caller]
+	fn unset(&'static self) -> ! {
+		violation::violation(ViolationKind::Uninitialized, self.0.inner.name())
+	}
+
+	#[inline]
+	#[track_caller]
+	/// Sets the value of the singleton.
+	///
+	/// Panics if the singleton has already been set.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn set_once(&'static self, val: T) {
+		if self.0.try_init(val).is_err() {
+			violation::violation(ViolationKind::AlreadyInitialized, self.0.inner.name());
+		}
+	}
+
+	#[inline]
+	/// Tests if the singleton has been set.
+	pub fn is_set(&'static self) -> bool {
+		self.0.is_initialized()
+	}
+
+	#[inline]
+	#[track_caller]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires an **immutable reference** to the singleton.
+	///
+	/// Panics if the singleton has not been set yet.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn get(&'static self) -> SinglytonRef<'static, T> {
+		match self.0.try_get_opt() {
+			Some(val) => val,
+			None => self.unset()
+		}
+	}
+
+	#[inline]
+	#[track_caller]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **mutable reference** to the singleton.
+	///
+	/// Panics if the singleton has not been set yet.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn get_mut(&'static self) -> SinglytonRefMut<'static, T> {
+		if !self.is_set() {
+			self.unset();
+		}
+		self.0.get_mut()
+	}
+}
\ No newline at end of file
@@ -0,0 +1,93 @@
+use crate::{Singleton, SingletonUninit};
+use alloc::vec::Vec;
+
+/// A singleton which can be deinitialized, returning it to uninitialized memory.
+pub trait Deinit: Sync {
+	fn deinit(&'static self);
+}
+
+impl<T> Deinit for SingletonUninit<T>
+where
+	SingletonUninit<T>: Sync
+{
+	#[inline]
+	fn deinit(&'static self) {
+		SingletonUninit::deinit(self)
+	}
+}
+
+/// A registry of singletons which are deinitialized together, in a deterministic order.
+///
+/// Statics are never dropped, so resources held in a `SingletonUninit` (a logger, a network connection, etc.) are never torn down unless you do it yourself. Register them here and call `shutdown` before exiting.
+///
+/// Singletons with a **higher** priority are deinitialized **first**. Singletons with equal priorities are deinitialized in the order they were registered.
+///
+/// # Lifecycle
+///
+/// Rust gives no guarantees about the order in which statics are destroyed (and never destroys most of them), so deterministic teardown of interdependent singletons must be explicit:
+///
+/// 1. **Initialization**: initialize each singleton with `init`, which also registers it. Give singletons which depend on others a higher priority than their dependencies.
+/// 2. **Shutdown**: call `shutdown` once, before exiting. Every singleton is dropped exactly once, while the singletons it depends on are still initialized.
+///
+/// After `shutdown`, the group is empty and its singletons are uninitialized, so the lifecycle can start again.
+///
+/// Requires the `alloc` feature.
+///
+/// # Panics
+///
+/// In debug builds, usage of this abstraction is checked for safety at runtime.
+///
+/// * Using this struct across threads will panic, once a thread id provider has been set with `set_thread_id_provider`.
+///
+/// Shutting down a group containing a singleton that is not initialized will panic in every build.
+pub struct DropGroup(Singleton<Vec<(u32, &'static dyn Deinit)>>);
+
+impl DropGroup {
+	#[inline]
+	pub const fn new() -> Self {
+		Self(Singleton::new(Vec::new()))
+	}
+
+	#[inline]
+	/// Initializes a singleton and registers it to be deinitialized when this group is shut down.
+	///
+	/// In debug builds, this will panic if the singleton is **already initialized**, it is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	pub fn init<T>(&'static self, singleton: &'static SingletonUninit<T>, val: T, priority: u32)
+	where
+		SingletonUninit<T>: Sync
+	{
+		singleton.init(val);
+		self.register(singleton, priority);
+	}
+
+	#[inline]
+	/// Registers a singleton to be deinitialized when this group is shut down.
+	pub fn register(&'static self, singleton: &'static dyn Deinit, priority: u32) {
+		self.0.get_mut().push((priority, singleton));
+	}
+
+	/// Deinitializes every registered singleton in descending priority order, then clears the group.
+	///
+	/// Panics if any registered singleton is not initialized. The singletons which had not been deinitialized yet stay registered, so that they can still be shut down afterwards.
+	pub fn shutdown(&'static self) {
+		self.0.get_mut().sort_by(|(a, _), (b, _)| b.cmp(a));
+		loop {
+			// Each singleton is unregistered just before it is deinitialized, so a panic leaves the rest of the group intact
+			let mut singletons = self.0.get_mut();
+			if singletons.is_empty() {
+				break;
+			}
+			let (_, singleton) = singletons.remove(0);
+			drop(singletons);
+
+			singleton.deinit();
+		}
+	}
+}
+
+impl Default for DropGroup {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
\ No newline at end of file
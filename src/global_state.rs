@@ -0,0 +1,35 @@
+use crate::SingletonUninit;
+
+/// A type with a single global instance, which is implemented with the [`global_state!`](crate::global_state) macro.
+///
+/// This gives framework-style access to global state, such as `Config::instance().get()`, without declaring or naming the underlying static.
+///
+/// # Panics
+///
+/// In debug builds, usage of this abstraction is checked for safety at runtime, just like `SingletonUninit`.
+///
+/// * Using the instance across threads will panic, once a thread id provider has been set with `set_thread_id_provider`.
+/// * Using the instance before calling `initialize` or after calling `shutdown` will panic.
+/// * Calling `initialize` more than once without calling `shutdown` in between will panic.
+pub trait GlobalState: Sized + 'static {
+	/// The singleton holding the global instance of this type.
+	fn instance() -> &'static SingletonUninit<Self>;
+
+	#[inline]
+	/// Initializes the global instance of this type.
+	///
+	/// In debug builds, this will panic if the instance is **already initialized**, it is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	fn initialize(val: Self) {
+		Self::instance().init(val)
+	}
+
+	#[inline]
+	/// Drops the global instance of this type. It can be initialized again afterwards with `initialize`.
+	///
+	/// Panics if the instance is not initialized.
+	///
+	/// In debug builds, this will panic if the instance is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	fn shutdown() {
+		Self::instance().deinit()
+	}
+}
\ No newline at end of file
@@ -0,0 +1,215 @@
+use core::pin::Pin;
+use crate::{SinglytonCell, SinglytonRef, SinglytonRefMut, map_ref, map_ref_mut, violation::{self, ViolationKind}};
+
+enum State<T, F> {
+	Uninit(F),
+	#[cfg(not(any(feature = "sync", feature = "spin")))]
+	Initializing,
+	Init(T),
+	Poisoned
+}
+impl<T, F> State<T, F> {
+	#[inline]
+	fn get(&self, name: Option<&'static str>) -> &T {
+		match self {
+			State::Init(val) => val,
+			_ => self.uninit(name)
+		}
+	}
+
+	#[inline]
+	fn get_mut(&mut self, name: Option<&'static str>) -> &mut T {
+		match self {
+			State::Init(val) => val,
+			_ => self.uninit(name)
+		}
+	}
+
+	#[cold]
+	#[inline(never)]
+	fn uninit(&self, name: Option<&'static str>) -> ! {
+		#[cfg(not(any(feature = "sync", feature = "spin")))]
+		if let State::Initializing = self {
+			violation::violation(ViolationKind::RecursiveInitialization, name);
+		}
+		violation::violation(ViolationKind::Poisoned, name)
+	}
+}
+
+// Poisons the singleton if its initializer panics.
+#[cfg(not(any(feature = "sync", feature = "spin")))]
+struct PoisonOnUnwind<'a, T, F>(&'a SinglytonCell<State<T, F>>);
+#[cfg(not(any(feature = "sync", feature = "spin")))]
+impl<T, F> Drop for PoisonOnUnwind<'_, T, F> {
+	#[inline]
+	fn drop(&mut self) {
+		let mut state = self.0.get_mut();
+		if let State::Initializing = &*state {
+			*state = State::Poisoned;
+		}
+	}
+}
+
+/// A **thread-unsafe** global singleton which is lazily initialized on first access.
+///
+/// Using this across threads is undefined behaviour.
+///
+/// # Panics
+///
+/// In debug builds, usage of this abstraction is checked for safety at runtime.
+///
+/// * Using this struct across threads will panic, once a thread id provider has been set with `set_thread_id_provider`.
+/// * Mixing mutabilty of borrows will panic
+/// * Accessing the singleton from within its own initializer will panic with a "recursive lazy initialization" message, even in release builds.
+///
+/// If the initializer panics, every subsequent access will panic too. Like other violations, these are reported as a `BorrowViolation` with the `structured-panic` feature, and abort the process with the `abort-on-violation` feature.
+///
+/// The initializer may freely access other singletons. With the `sync` or `spin` features, the singleton stays locked while its initializer runs so that other threads wait for it, so accessing it from within its own initializer will deadlock instead.
+///
+/// # Pinning
+///
+/// The value is never moved once initialized, so it can be pinned with `get_pin` and `get_pin_mut`. This makes this singleton suitable for `!Unpin` values, such as global futures or intrusive data structures. For the same reason, `get_mut` requires `T: Unpin`.
+pub struct SingletonLazy<T, F = fn() -> T>(SinglytonCell<State<T, F>>);
+
+impl<T, F: FnOnce() -> T> SingletonLazy<T, F> {
+	#[inline]
+	pub const fn new(init: F) -> Self {
+		Self(SinglytonCell::new(State::Uninit(init)))
+	}
+
+	#[cfg(not(any(feature = "sync", feature = "spin")))]
+	fn force(&'static self) {
+		if let State::Init(_) = &*self.0.get() {
+			return;
+		}
+
+		let init = {
+			let mut state = self.0.get_mut();
+			match core::mem::replace(&mut *state, State::Initializing) {
+				State::Uninit(init) => init,
+				other => {
+					*state = other;
+					return;
+				}
+			}
+		};
+
+		// The borrow is released while the initializer runs, so that it can access other singletons, and so that accessing this one is detected as recursion
+		let poison = PoisonOnUnwind(&self.0);
+		let val = init();
+		*self.0.get_mut() = State::Init(val);
+		drop(poison);
+	}
+
+	#[cfg(any(feature = "sync", feature = "spin"))]
+	fn force(&'static self) {
+		if let State::Init(_) = &*self.0.get() {
+			return;
+		}
+
+		// The lock is held while the initializer runs, so that other threads wait for it
+		let mut state = self.0.get_mut();
+		// Another thread may have initialized the singleton in the meantime
+		if let State::Uninit(_) = &*state {
+			if let State::Uninit(init) = core::mem::replace(&mut *state, State::Poisoned) {
+				*state = State::Init(init());
+			}
+		}
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires an **immutable reference** to the singleton, initializing it first if this is the first access.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn get(&'static self) -> SinglytonRef<'static, T> {
+		self.force();
+		map_ref(self.0.get(), |state| state.get(self.0.name()))
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **mutable reference** to the singleton, initializing it first if this is the first access.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn get_mut(&'static self) -> SinglytonRefMut<'static, T>
+	where
+		T: Unpin
+	{
+		self.force();
+		map_ref_mut(self.0.get_mut(), |state| state.get_mut(self.0.name()))
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **pinned immutable reference** to the singleton, initializing it first if this is the first access.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn get_pin(&'static self) -> Pin<SinglytonRef<'static, T>> {
+		unsafe { Pin::new_unchecked(self.get()) }
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **pinned mutable reference** to the singleton, initializing it first if this is the first access.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn get_pin_mut(&'static self) -> Pin<SinglytonRefMut<'static, T>> {
+		self.force();
+		unsafe { Pin::new_unchecked(map_ref_mut(self.0.get_mut(), |state| state.get_mut(self.0.name()))) }
+	}
+
+	#[inline]
+	/// Acquires an **immutable pointer** to the singleton, initializing it first if this is the first access.
+	///
+	/// Like `get`, this runs the initializer if the singleton has not been initialized yet. Use `try_ptr` to get a pointer without that side effect.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	///
+	/// # Safety
+	///
+	/// This is unsafe because the returned pointer bypasses any future borrow checking.
+	pub unsafe fn force_ptr(&'static self) -> *const T {
+		&*self.get() as *const T
+	}
+
+	#[inline]
+	/// Acquires an **immutable pointer** to the singleton, or returns `None` without running the initializer if it has not been initialized yet.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	///
+	/// # Safety
+	///
+	/// This is unsafe because the returned pointer bypasses any future borrow checking.
+	pub unsafe fn try_ptr(&'static self) -> Option<*const T> {
+		match &*self.0.get() {
+			State::Init(val) => Some(val as *const T),
+			_ => None
+		}
+	}
+
+	#[inline]
+	/// Initializes the singleton with `val` instead of running its initializer, if it has not been initialized yet.
+	///
+	/// Returns `Err(val)`, handing the value back, if the singleton has already been initialized (or its initializer is running or has panicked). This is useful in tests, to inject a value so that an expensive initializer never runs.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn set(&'static self, val: T) -> Result<(), T> {
+		let mut state = self.0.get_mut();
+		match &*state {
+			State::Uninit(_) => {
+				*state = State::Init(val);
+				Ok(())
+			},
+			_ => Err(val)
+		}
+	}
+
+	#[inline]
+	/// Tests if the singleton has been initialized.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn is_initialized(&'static self) -> bool {
+		matches!(&*self.0.get(), State::Init(_))
+	}
+}
\ No newline at end of file
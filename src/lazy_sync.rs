@@ -0,0 +1,44 @@
+use core::cell::Cell;
+use once_cell::sync::OnceCell;
+use crate::violation::{self, ViolationKind};
+
+/// A **thread-safe** global singleton which is lazily initialized on first access.
+///
+/// Unlike the other singletons in this crate, this is backed by `once_cell::sync::OnceCell` and never hands out borrow guards, so it has no thread checks and can be genuinely shared between threads. If several threads race to access it first, only one of them runs the initializer and the rest block until it is done.
+///
+/// The value can never be mutated once initialized, so this is suited to read-mostly globals such as lookup tables and parsed configuration.
+///
+/// Requires the `once_cell` feature.
+pub struct SingletonLazySync<T, F = fn() -> T> {
+	cell: OnceCell<T>,
+	init: Cell<Option<F>>
+}
+// The initializer is only ever taken by the single thread which initializes the cell.
+unsafe impl<T: Send + Sync, F: Send> Sync for SingletonLazySync<T, F> {}
+
+impl<T, F: FnOnce() -> T> SingletonLazySync<T, F> {
+	#[inline]
+	pub const fn new(init: F) -> Self {
+		Self {
+			cell: OnceCell::new(),
+			init: Cell::new(Some(init))
+		}
+	}
+
+	#[inline]
+	/// Acquires an **immutable reference** to the singleton, initializing it first if this is the first access.
+	///
+	/// This will panic if the initializer panicked during a previous access.
+	pub fn get(&'static self) -> &'static T {
+		self.cell.get_or_init(|| match self.init.take() {
+			Some(init) => init(),
+			None => violation::violation(ViolationKind::Poisoned, None)
+		})
+	}
+
+	#[inline]
+	/// Tests if the singleton has been initialized.
+	pub fn is_initialized(&'static self) -> bool {
+		self.cell.get().is_some()
+	}
+}
\ No newline at end of file
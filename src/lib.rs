@@ -1,16 +1,98 @@
-#![cfg_attr(not(test), no_std)]
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![doc = include_str!("../README.md")]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg(test)]
 mod tests;
 
+mod lock;
+
 mod cell;
 use cell::*;
-pub use cell::{map_ref, map_ref_mut, SinglytonRef, SinglytonRefMut};
 
-#[cfg(debug_assertions)]
-use core::cell::UnsafeCell;
+mod violation;
+pub use violation::{BorrowViolation, ViolationKind};
+
+mod thread;
+pub use thread::set_thread_id_provider;
+pub use cell::{map_ref, map_ref_mut, SinglytonRef, SinglytonRefMut, SinglytonUpgradableRef};
+
+mod reentrant;
+pub use reentrant::SingletonReentrant;
+
+mod lazy;
+pub use lazy::SingletonLazy;
+
+mod array;
+pub use array::SingletonArray;
+
+mod deferred;
+pub use deferred::DeferredSingleton;
+
+mod scope;
+pub use scope::BorrowScope;
+
+mod global_state;
+pub use global_state::GlobalState;
+
+mod macros;
+
+#[cfg(feature = "alloc")]
+mod drop_group;
+#[cfg(feature = "alloc")]
+pub use drop_group::{DropGroup, Deinit};
+
+#[cfg(feature = "std")]
+mod thread_local;
+#[cfg(feature = "std")]
+pub use thread_local::ThreadLocalSingleton;
+
+#[cfg(feature = "alloc")]
+mod snapshot;
+#[cfg(feature = "alloc")]
+pub use snapshot::{SnapshotHistory, SnapshotId};
+
+#[cfg(feature = "sync")]
+mod sharded;
+#[cfg(feature = "sync")]
+pub use sharded::ShardedSingleton;
+
+#[cfg(feature = "alloc")]
+mod atomic_box;
+#[cfg(feature = "alloc")]
+pub use atomic_box::{SingletonAtomicBox, SingletonOptionAtomic};
+
+#[cfg(feature = "alloc")]
+mod slice;
+#[cfg(feature = "alloc")]
+pub use slice::SingletonSlice;
+
+#[cfg(feature = "alloc")]
+mod boxed;
+#[cfg(feature = "alloc")]
+pub use boxed::SingletonBoxed;
+
+#[cfg(feature = "once_cell")]
+mod lazy_sync;
+#[cfg(feature = "once_cell")]
+pub use lazy_sync::SingletonLazySync;
+
 use core::mem::MaybeUninit;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "observers")]
+use alloc::boxed::Box;
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "observers")]
+use core::sync::atomic::AtomicPtr;
+
+#[cfg(feature = "observers")]
+struct Observer<T> {
+	observer: fn(&T),
+	next: *const Observer<T>
+}
 
 /// A **thread-unsafe** global singleton.
 ///
@@ -20,32 +102,353 @@ use core::mem::MaybeUninit;
 ///
 /// In debug builds, usage of this abstraction is checked for safety at runtime.
 ///
-/// * Using this struct across threads will panic.
+/// * Using this struct across threads will panic, once a thread id provider has been set with `set_thread_id_provider`.
 /// * Mixing mutabilty of borrows will panic (this is bypassed if you are using the pointer getters)
-#[repr(transparent)]
-pub struct Singleton<T>(SinglytonCell<T>);
-unsafe impl<T> Sync for Singleton<T> {}
+///
+/// # Statics
+///
+/// Every accessor takes `&'static self`, so a singleton on the stack is rejected at compile time rather than at runtime:
+///
+/// ```compile_fail
+/// let singleton = singlyton::Singleton::new(0_u32);
+/// *singleton.get_mut() += 1;
+/// ```
+///
+/// # Thread safety
+///
+/// With the `sync` feature enabled, singletons are backed by a `RwLock` and can be shared between threads, so `T` must be `Send + Sync`. Conflicting borrows block until released rather than panicking.
+///
+/// The `spin` feature does the same without `std`, spinning on conflicting borrows instead.
+///
+#[cfg_attr(any(feature = "sync", feature = "spin"), doc = "```compile_fail")]
+#[cfg_attr(not(any(feature = "sync", feature = "spin")), doc = "```")]
+/// static SINGLETON: singlyton::Singleton<*mut u8> = singlyton::Singleton::new(core::ptr::null_mut());
+/// ```
+///
+/// # Async
+///
+/// Holding a guard across an `.await` is a footgun, even on a single-threaded runtime: while the task is suspended, any other task which borrows the singleton will panic in debug builds (or deadlock with the `sync` or `spin` features).
+///
+/// In async code, prefer `with` and `with_mut`. Their closures are synchronous and cannot return anything borrowed from the singleton, so the borrow can never be held across an `.await` by construction:
+///
+/// ```
+/// # use singlyton::Singleton;
+/// static REQUESTS: Singleton<u32> = Singleton::new(0);
+///
+/// async fn handle_request() {
+///     let request = REQUESTS.with_mut(|requests| {
+///         *requests += 1;
+///         *requests
+///     });
+///     some_io(request).await;
+/// }
+/// # async fn some_io(_: u32) {}
+/// ```
+///
+/// ```compile_fail
+/// # use singlyton::Singleton;
+/// static REQUESTS: Singleton<u32> = Singleton::new(0);
+///
+/// async fn handle_request() {
+///     // The reference cannot escape into the returned future
+///     REQUESTS.with_mut(|requests| async move { *requests += 1 }).await;
+/// }
+/// ```
+///
+/// Guards are also `!Send`, so a future which holds one across an `.await` cannot be spawned on a multi-threaded runtime:
+///
+/// ```compile_fail
+/// # use singlyton::Singleton;
+/// static REQUESTS: Singleton<u32> = Singleton::new(0);
+///
+/// fn spawn<F: core::future::Future + Send>(_: F) {}
+/// async fn some_io() {}
+///
+/// spawn(async {
+///     let mut requests = REQUESTS.get_mut();
+///     some_io().await;
+///     *requests += 1;
+/// });
+/// ```
+///
+/// Clippy can also flag guards which are held across an `.await`, by adding them to `await-holding-invalid-types` in `clippy.toml`:
+///
+/// ```toml
+/// await-holding-invalid-types = ["singlyton::SinglytonRef", "singlyton::SinglytonRefMut"]
+/// ```
+///
+/// # Layout
+///
+/// In release builds without the `observers`, `sync` or `spin` features, `Singleton<T>` is `#[repr(transparent)]` over an `UnsafeCell<T>`, so it has the same size, alignment and ABI as `T` and can be embedded in `#[repr(C)]` structs that are shared with C. Use `as_raw` to get the address of the contained value.
+///
+/// In debug builds, or with the `observers`, `sync` or `spin` features, the layout is unspecified, so code relying on it for FFI must be built in release without them.
+///
+/// # Global allocators
+///
+/// Borrow checks are a single atomic counter and never allocate or touch thread-local storage, so singletons can back the internal state of a `#[global_allocator]` without recursing into it.
+///
+/// Violations still panic, and panicking allocates, so the allocator must never hold conflicting borrows. Registering observers with `add_observer` makes `get_mut` allocate. With the `sync` feature, blocking on the `RwLock` is left to the platform, which is not guaranteed to be allocation-free. With the `backtrace` feature, every borrow captures a backtrace, which allocates, so it cannot be used with singletons inside a global allocator.
+///
+#[cfg_attr(feature = "backtrace", doc = "```ignore")]
+#[cfg_attr(not(feature = "backtrace"), doc = "```")]
+/// use std::alloc::{GlobalAlloc, Layout, System};
+/// use singlyton::Singleton;
+///
+/// static ALLOCATED: Singleton<usize> = Singleton::new(0);
+///
+/// struct CountingAllocator;
+/// unsafe impl GlobalAlloc for CountingAllocator {
+///     unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+///         *ALLOCATED.get_mut() += layout.size();
+///         System.alloc(layout)
+///     }
+///
+///     unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+///         *ALLOCATED.get_mut() -= layout.size();
+///         System.dealloc(ptr, layout)
+///     }
+/// }
+///
+/// #[global_allocator]
+/// static ALLOCATOR: CountingAllocator = CountingAllocator;
+///
+/// let before = *ALLOCATED.get();
+/// let buffer = vec![0u8; 1024];
+/// assert_eq!(*ALLOCATED.get(), before + 1024);
+/// drop(buffer);
+/// assert_eq!(*ALLOCATED.get(), before);
+/// ```
+#[cfg_attr(not(feature = "observers"), repr(transparent))]
+pub struct Singleton<T>(SinglytonCell<T>, #[cfg(feature = "observers")] AtomicPtr<Observer<T>>);
 
+/// Wraps a value in a singleton at runtime, such as in builders and test fixtures.
+///
+/// This is not a `const fn`, so it cannot be used to initialize a `static`; use `Singleton::new` for that. As every accessor takes `&'static self`, a singleton created at runtime must be leaked (or otherwise given a `'static` lifetime) before it can be accessed.
+impl<T> From<T> for Singleton<T> {
+	#[inline]
+	fn from(val: T) -> Self {
+		Self::new(val)
+	}
+}
 impl<T> Singleton<T> {
 	#[inline]
+	#[cfg(not(feature = "observers"))]
 	pub const fn new(val: T) -> Self {
 		Self(SinglytonCell::new(val))
 	}
 
 	#[inline]
+	#[cfg(feature = "observers")]
+	pub const fn new(val: T) -> Self {
+		Self(SinglytonCell::new(val), AtomicPtr::new(core::ptr::null_mut()))
+	}
+
+	#[inline]
+	#[cfg(not(feature = "observers"))]
+	/// Creates a singleton with a name, which is included in the panic messages of its debug checks. See also `named_singleton!`.
+	///
+	/// The name is only stored in debug builds.
+	pub const fn new_named(val: T, name: &'static str) -> Self {
+		Self(SinglytonCell::new_named(val, name))
+	}
+
+	#[inline]
+	#[cfg(feature = "observers")]
+	/// Creates a singleton with a name, which is included in the panic messages of its debug checks. See also `named_singleton!`.
+	///
+	/// The name is only stored in debug builds.
+	pub const fn new_named(val: T, name: &'static str) -> Self {
+		Self(SinglytonCell::new_named(val, name), AtomicPtr::new(core::ptr::null_mut()))
+	}
+
+	#[inline]
+	/// Acquires an **immutable reference** to the singleton without any borrow checking, in a `const` context.
+	///
+	/// This is intended for building `const` tables from the initial value of a singleton. The compiler rejects reading from a `static` in a `const` context, so keep the initializer in a `const` and peek at that instead:
+	///
+	/// ```
+	/// use singlyton::Singleton;
+	///
+	/// const INITIAL: Singleton<[u32; 3]> = Singleton::new([1, 2, 3]);
+	/// static SINGLETON: Singleton<[u32; 3]> = INITIAL;
+	///
+	/// const LEN: usize = unsafe { INITIAL.peek() }.len();
+	/// static TABLE: [u32; LEN] = [0; LEN];
+	/// ```
+	///
+	/// # Safety
+	///
+	/// This is unsafe because the returned reference bypasses borrow checking. The singleton must not be mutated for as long as the reference is alive.
+	pub const unsafe fn peek(&self) -> &T {
+		self.0.peek()
+	}
+
+	#[inline]
+	/// Acquires a **mutable pointer** to the singleton without any borrow checking.
+	///
+	/// This is intended for handing the address of the contained value to C. Dereferencing the pointer is subject to the same rules as `as_mut_ptr`.
+	pub const fn as_raw(&self) -> *mut T {
+		self.0.as_raw()
+	}
+
+	#[inline]
+	#[cfg(feature = "test-util")]
+	/// Forgets which thread the singleton belongs to, so that the next thread to borrow it becomes its owner.
+	///
+	/// This is a test utility for harnesses which reuse the same static across tests that each run on a fresh thread. It has no effect unless a thread id provider has been set with `set_thread_id_provider`, and in release builds or with the `sync` or `spin` features.
+	pub fn reset_thread(&'static self) {
+		self.0.reset_thread()
+	}
+
+	#[inline]
+	/// Tests if `a` and `b` are the same singleton, without accessing their contents.
+	///
+	/// This is useful for registries keyed by singleton identity. Note that in release builds, distinct singletons of zero-sized types may share an address.
+	pub fn ptr_eq(a: &'static Self, b: &'static Self) -> bool {
+		core::ptr::eq(a, b)
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
 	/// Acquires an **immutable reference** to the singleton.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
-	pub fn get(&'static self) -> SinglytonRef<T> {
+	pub fn get(&'static self) -> SinglytonRef<'static, T> {
 		self.0.get()
 	}
 
 	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
 	/// Acquires a **mutable reference** to the singleton.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
-	pub fn get_mut(&'static self) -> SinglytonRefMut<T> {
-		self.0.get_mut()
+	pub fn get_mut(&'static self) -> SinglytonRefMut<'static, T> {
+		self.observe(self.0.get_mut())
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires an **immutable reference** to the singleton which cannot outlive `scope`.
+	///
+	/// See `BorrowScope`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn get_scoped<'s>(&'static self, scope: &'s BorrowScope) -> SinglytonRef<'s, T> {
+		let _ = scope;
+		SinglytonRef::shorten(self.get())
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **mutable reference** to the singleton which cannot outlive `scope`.
+	///
+	/// See `BorrowScope`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn get_mut_scoped<'s>(&'static self, scope: &'s BorrowScope) -> SinglytonRefMut<'s, T> {
+		let _ = scope;
+		SinglytonRefMut::shorten(self.get_mut())
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires an **immutable reference** to a part of the singleton, such as one of its fields.
+	///
+	/// This composes with interior mutability: if the field is a `Cell` or `RefCell`, it can be mutated through the returned reference while the singleton itself is only immutably borrowed, so other immutable borrows of the singleton can still be held. With the `sync` or `spin` features, `Cell` and `RefCell` are rejected, as they are not `Sync`.
+	///
+	#[cfg_attr(any(feature = "sync", feature = "spin"), doc = "```compile_fail")]
+	#[cfg_attr(not(any(feature = "sync", feature = "spin")), doc = "```")]
+	/// # use singlyton::Singleton;
+	/// use core::cell::Cell;
+	///
+	/// struct Stats {
+	///     name: &'static str,
+	///     hits: Cell<u32>
+	/// }
+	///
+	/// static STATS: Singleton<Stats> = Singleton::new(Stats { name: "cache", hits: Cell::new(0) });
+	///
+	/// let stats = STATS.get();
+	/// let hits = STATS.project(|stats| &stats.hits);
+	/// hits.set(hits.get() + 1);
+	/// debug_assert_eq!(stats.hits.get(), 1);
+	/// ```
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn project<U: ?Sized, F>(&'static self, f: F) -> SinglytonRef<'static, U>
+	where
+		F: FnOnce(&T) -> &U
+	{
+		map_ref(self.0.get(), f)
+	}
+
+	#[inline]
+	#[cfg(debug_assertions)]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **mutable reference** to the singleton, retrying up to `spins` times (yielding in between) if it is currently borrowed.
+	///
+	/// This is a diagnostic aid for single-threaded async runtimes and tests, where a borrow is expected to be released imminently by cooperative scheduling and an immediate panic would hide a logic deadlock. Only available in debug builds.
+	///
+	/// In debug builds, this will panic if the singleton is still borrowed after `spins` retries.
+	pub fn get_mut_blocking(&'static self, spins: u32) -> SinglytonRefMut<'static, T> {
+		for _ in 0..spins {
+			if let Some(guard) = self.0.try_get_mut() {
+				return self.observe(guard);
+			}
+
+			#[cfg(feature = "std")]
+			std::thread::yield_now();
+			#[cfg(not(feature = "std"))]
+			core::hint::spin_loop();
+		}
+
+		self.get_mut()
+	}
+
+	#[inline]
+	fn observe(&'static self, guard: SinglytonRefMut<'static, T>) -> SinglytonRefMut<'static, T> {
+		#[cfg(feature = "observers")]
+		if !self.1.load(Ordering::Acquire).is_null() {
+			return SinglytonRefMut::notify(guard, Notify::new(self));
+		}
+		guard
+	}
+
+	#[inline]
+	#[cfg(feature = "observers")]
+	/// Registers an observer which is called with an **immutable reference** to the singleton after every **mutable reference** acquired with `get_mut` is dropped.
+	///
+	/// This is useful for reactive globals, such as caches or UI state that must be refreshed when the singleton changes.
+	///
+	/// Observers are notified in reverse order of registration, and cannot be unregistered. They must not mutably access the singleton.
+	///
+	/// Requires the `observers` feature. Without it, singletons have no storage for observers, so mutable references carry no overhead for them.
+	pub fn add_observer(&'static self, observer: fn(&T)) {
+		let node = Box::into_raw(Box::new(Observer {
+			observer,
+			next: core::ptr::null()
+		}));
+
+		let mut next = self.1.load(Ordering::Relaxed);
+		loop {
+			unsafe { (*node).next = next };
+
+			match self.1.compare_exchange_weak(next, node, Ordering::AcqRel, Ordering::Relaxed) {
+				Ok(_) => break,
+				Err(head) => next = head
+			}
+		}
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires an **immutable reference** to the singleton which can later be upgraded to a **mutable reference** with `SinglytonUpgradableRef::upgrade`.
+	///
+	/// This is useful when you only need to mutate the singleton after inspecting it. With the `sync` or `spin` features, the singleton is held exclusively until the guard is dropped, so other readers wait for it.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn upgradable_read(&'static self) -> SinglytonUpgradableRef<'static, T> {
+		SinglytonUpgradableRef::new(self)
 	}
 
 	#[inline]
@@ -53,6 +456,8 @@ impl<T> Singleton<T> {
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
 	///
+	/// # Safety
+	///
 	/// This is unsafe because the returned pointer bypasses any future borrow checking.
 	pub unsafe fn as_ptr(&'static self) -> *const T {
 		&*self.0.get() as *const T
@@ -63,17 +468,410 @@ impl<T> Singleton<T> {
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
 	///
+	/// # Safety
+	///
 	/// This is unsafe because the returned pointer bypasses any future borrow checking.
 	pub unsafe fn as_mut_ptr(&'static self) -> *mut T {
 		&mut *self.0.get_mut() as *mut T
 	}
 
 	#[inline]
-	/// Replaces the value in the singleton with anew.
+	/// Acquires an **immutable pointer** to the singleton, or returns `None` instead of panicking if a mutable reference is currently held.
+	///
+	/// This is intended for FFI callbacks, where unwinding across the C ABI is undefined behaviour. In release builds without the `sync` or `spin` features, this always returns `Some`.
+	///
+	/// # Safety
+	///
+	/// This is unsafe because the returned pointer bypasses any future borrow checking.
+	pub unsafe fn try_as_ptr(&'static self) -> Option<*const T> {
+		self.0.try_get().map(|val| &*val as *const T)
+	}
+
+	#[inline]
+	/// Acquires a **mutable pointer** to the singleton, or returns `None` instead of panicking if an existing mutable or immutable reference is currently held.
+	///
+	/// This is intended for FFI callbacks, where unwinding across the C ABI is undefined behaviour. In release builds without the `sync` or `spin` features, this always returns `Some`.
+	///
+	/// # Safety
+	///
+	/// This is unsafe because the returned pointer bypasses any future borrow checking.
+	pub unsafe fn try_as_mut_ptr(&'static self) -> Option<*mut T> {
+		self.0.try_get_mut().map(|mut val| &mut *val as *mut T)
+	}
+
+	#[inline]
+	/// Replaces the value in the singleton with anew, returning the previous value.
+	///
+	/// The borrow is released before the previous value is returned, so its `Drop` implementation may access the singleton.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn replace(&'static self, val: T) -> T {
+		core::mem::replace(&mut *self.get_mut(), val)
+	}
+
+	#[inline]
+	#[must_use = "use `replace` if the guard is not needed"]
+	/// Replaces the value in the singleton with anew, then returns a **mutable reference** to the new value under the same borrow.
+	///
+	/// This is useful for setting a global and immediately continuing to mutate it, as the borrow (and its debug checks) is only acquired once. Unlike `replace`, the previous value is dropped while the singleton is still borrowed, so its `Drop` implementation must not access the singleton.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn replace_get_mut(&'static self, val: T) -> SinglytonRefMut<'static, T> {
+		let mut guard = self.get_mut();
+		*guard = val;
+		guard
+	}
+
+	#[inline]
+	/// Returns a clone of the value in the singleton, which it can later be rewound to with `restore`.
+	///
+	/// This is useful for capturing global state at frame boundaries for deterministic replay. See `SnapshotHistory` for keeping several snapshots.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn snapshot(&'static self) -> T
+	where
+		T: Clone
+	{
+		self.0.get().clone()
+	}
+
+	#[inline]
+	/// Rewinds the singleton to a value captured with `snapshot`, dropping the current value.
+	///
+	/// The borrow is released before the current value is dropped, so its `Drop` implementation may access the singleton.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn restore(&'static self, snapshot: T) {
+		drop(self.replace(snapshot));
+	}
+
+	#[inline]
+	/// Replaces the value in the singleton with `T::default()`, returning the previous value.
+	///
+	/// This is useful for resetting global state in between tests.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn reset(&'static self) -> T
+	where
+		T: Default
+	{
+		self.take()
+	}
+
+	#[inline]
+	/// Takes the value out of the singleton, leaving `T::default()` in its place.
+	///
+	/// This is useful for draining globals such as a queue of pending events.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn take(&'static self) -> T
+	where
+		T: Default
+	{
+		core::mem::take(&mut *self.get_mut())
+	}
+
+	#[inline]
+	/// Swaps the value in the singleton with `local` under a single mutable borrow.
+	///
+	/// This is useful for temporarily moving global state into a local for processing, and swapping it back afterwards.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn swap_local(&'static self, local: &mut T) {
+		core::mem::swap(&mut *self.get_mut(), local);
+	}
+
+	#[inline]
+	/// Replaces the value in the singleton with `new` if it is equal to `expected`.
+	///
+	/// On success, returns `Ok` with the previous value. Otherwise, returns `Err` with a clone of the current value, leaving it unchanged. This is useful for optimistic transitions of global state machines.
+	///
+	/// The borrow is released before returning. Observers are only notified if the value was replaced.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
-	pub fn replace(&'static self, val: T) {
-		*self.0.get_mut() = val;
+	pub fn compare_exchange(&'static self, expected: &T, new: T) -> Result<T, T>
+	where
+		T: PartialEq + Clone
+	{
+		let current = self.0.get_mut();
+		if *current != *expected {
+			return Err(current.clone());
+		}
+		Ok(core::mem::replace(&mut *self.observe(current), new))
+	}
+
+	#[inline]
+	/// Returns the number of **immutable references** to the singleton which are currently held.
+	///
+	/// This is intended for instrumentation, such as finding hot spots where a singleton is borrowed many times at once. Borrows are only counted in debug builds and with the `spin` feature (without `sync`); otherwise, this always returns `0`.
+	pub fn immutable_borrow_count(&'static self) -> usize {
+		self.0.immutable_borrow_count()
+	}
+
+	#[inline]
+	/// Returns whether a **mutable reference** to the singleton is currently held.
+	///
+	/// Like `immutable_borrow_count`, this is only tracked in debug builds and with the `spin` feature (without `sync`); otherwise, this always returns `false`.
+	pub fn has_mutable_borrow(&'static self) -> bool {
+		self.0.has_mutable_borrow()
+	}
+
+	#[inline]
+	/// Projects to a value inside the singleton and returns a clone of it, releasing the borrow before returning.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn map_cloned<U: Clone, F>(&'static self, f: F) -> U
+	where
+		F: FnOnce(&T) -> &U
+	{
+		f(&*self.0.get()).clone()
+	}
+
+	#[inline]
+	/// Calls `f` with an **immutable reference** to the singleton, or calls `fallback` instead of panicking if a mutable reference is currently held.
+	///
+	/// This is useful for recovering from re-entrant access, such as a logger that is called while its own state is being mutated. In release builds without the `sync` or `spin` features, this always calls `f`. With them, this calls `fallback` rather than blocking.
+	///
+	/// The borrow is released before returning.
+	pub fn get_or_else<R, E, F>(&'static self, fallback: E, f: F) -> R
+	where
+		E: FnOnce() -> R,
+		F: FnOnce(&T) -> R
+	{
+		match self.0.try_get() {
+			Some(val) => f(&val),
+			None => fallback()
+		}
+	}
+
+	#[inline]
+	/// Calls `f` with an **immutable reference** to the singleton and returns its result, or returns `default` instead of panicking if a mutable reference is currently held.
+	///
+	/// This is useful for returning an owned projection of the singleton without exposing a guard. Like `get_or_else`, this always calls `f` in release builds without the `sync` or `spin` features, and returns `default` rather than blocking with them.
+	///
+	/// The borrow is released before returning.
+	pub fn map_or<U, F>(&'static self, default: U, f: F) -> U
+	where
+		F: FnOnce(&T) -> U
+	{
+		match self.0.try_get() {
+			Some(val) => f(&val),
+			None => default
+		}
+	}
+
+	#[inline]
+	/// Calls `f` with an **immutable reference** to the singleton and returns its result.
+	///
+	/// The borrow is released before returning, so this is safe to use in async code (see the [Async](#async) section).
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn with<R, F>(&'static self, f: F) -> R
+	where
+		F: FnOnce(&T) -> R
+	{
+		f(&self.get())
+	}
+
+	#[inline]
+	/// Calls `f` with a **mutable reference** to the singleton and returns its result.
+	///
+	/// The borrow is released before returning, so this is safe to use in async code (see the [Async](#async) section).
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn with_mut<R, F>(&'static self, f: F) -> R
+	where
+		F: FnOnce(&mut T) -> R
+	{
+		f(&mut self.get_mut())
+	}
+
+	#[inline]
+	/// Calls `f` with a single **mutable reference** to the singleton, for a batch of mutations.
+	///
+	/// This is the preferred pattern for hot loops which mutate a global many times, such as thousands of times per frame. Calling `get_mut` for every write acquires and releases the borrow (and runs its debug checks) every time, whereas this acquires it exactly once for the whole batch. Observers are also notified once, after the batch.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn batch<F>(&'static self, f: F)
+	where
+		F: FnOnce(&mut T)
+	{
+		self.with_mut(f)
+	}
+
+	#[inline]
+	/// Calls `f` with an **immutable reference** to the singleton and returns its result, or returns the violation without calling `f` if a mutable reference is currently held.
+	///
+	/// This is the recommended entry point for code which may re-enter the singleton. In release builds without the `sync` or `spin` features, this always calls `f`. With them, this returns an error rather than blocking.
+	///
+	/// The borrow is released before returning.
+	pub fn try_with<R, F>(&'static self, f: F) -> Result<R, BorrowViolation>
+	where
+		F: FnOnce(&T) -> R
+	{
+		match self.0.try_get() {
+			Some(val) => Ok(f(&val)),
+			None => Err(BorrowViolation::new(ViolationKind::AlreadyMutablyBorrowed, self.0.name()))
+		}
+	}
+
+	#[inline]
+	/// Calls `f` with a **mutable reference** to the singleton and returns its result, or returns the violation without calling `f` if any other reference is currently held.
+	///
+	/// This is the recommended entry point for code which may re-enter the singleton. In release builds without the `sync` or `spin` features, this always calls `f`. With them, this returns an error rather than blocking.
+	///
+	/// The borrow is released before returning.
+	pub fn try_with_mut<R, F>(&'static self, f: F) -> Result<R, BorrowViolation>
+	where
+		F: FnOnce(&mut T) -> R
+	{
+		match self.0.try_get_mut() {
+			Some(val) => Ok(f(&mut self.observe(val))),
+			None => Err(BorrowViolation::new(ViolationKind::AlreadyBorrowed, self.0.name()))
+		}
+	}
+
+	#[inline]
+	/// Calls the fallible `f` with an **immutable reference** to the singleton and returns its result.
+	///
+	/// This is useful for reading globals inside fallible functions, as the result can be propagated with `?` directly. The borrow is released before returning, whether `f` succeeds or fails.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn access<R, E, F>(&'static self, f: F) -> Result<R, E>
+	where
+		F: FnOnce(&T) -> Result<R, E>
+	{
+		f(&self.0.get())
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Singleton<Vec<T>> {
+	#[inline]
+	/// Appends a value to the back of the vector.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn push(&'static self, val: T) {
+		self.get_mut().push(val);
+	}
+
+	#[inline]
+	/// Removes the last value from the vector and returns it, or `None` if it is empty.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn pop(&'static self) -> Option<T> {
+		self.get_mut().pop()
+	}
+
+	#[inline]
+	/// Removes all values from the vector.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn clear(&'static self) {
+		self.get_mut().clear();
+	}
+
+	#[inline]
+	/// The number of values in the vector.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn len(&'static self) -> usize {
+		self.get().len()
+	}
+
+	#[inline]
+	/// Tests if the vector is empty.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn is_empty(&'static self) -> bool {
+		self.get().is_empty()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<U: ?Sized> Singleton<alloc::rc::Rc<U>> {
+	#[inline]
+	/// Clones the `Rc` in the singleton, releasing the borrow before returning.
+	///
+	/// This gives the caller their own owning handle to the shared value, so no guard needs to be held while using it.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn get_rc(&'static self) -> alloc::rc::Rc<U> {
+		self.0.get().clone()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<U: ?Sized> Singleton<alloc::sync::Arc<U>> {
+	#[inline]
+	/// Clones the `Arc` in the singleton, releasing the borrow before returning.
+	///
+	/// This gives the caller their own owning handle to the shared value, so no guard needs to be held while using it.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn get_arc(&'static self) -> alloc::sync::Arc<U> {
+		self.0.get().clone()
+	}
+}
+
+#[cfg(feature = "observers")]
+impl<T> Observed for Singleton<T> {
+	fn notify(&self) {
+		let value = self.0.get();
+		let mut node = self.1.load(Ordering::Acquire) as *const Observer<T>;
+		while let Some(observer) = unsafe { node.as_ref() } {
+			(observer.observer)(&value);
+			node = observer.next;
+		}
+	}
+}
+
+#[inline]
+/// Calls `f` with **immutable references** to two singletons, such as for reading two globals together.
+///
+/// Both borrows are released before returning. `a` and `b` may be the same singleton.
+///
+/// In debug builds, this will panic if either singleton is mutably accessed from a different thread or if a mutable reference to either is currently held.
+pub fn with_both<A, B, R, F>(a: &'static Singleton<A>, b: &'static Singleton<B>, f: F) -> R
+where
+	F: FnOnce(&A, &B) -> R
+{
+	f(&a.get(), &b.get())
+}
+
+#[inline]
+/// Calls `f` with **mutable references** to two singletons, such as for moving state from one global to another.
+///
+/// Both borrows are released before returning.
+///
+/// In debug builds, this will panic if `a` and `b` are the same singleton, either singleton is mutably accessed from a different thread, or an existing mutable or immutable reference to either is currently held.
+pub fn with_both_mut<A, B, R, F>(a: &'static Singleton<A>, b: &'static Singleton<B>, f: F) -> R
+where
+	F: FnOnce(&mut A, &mut B) -> R
+{
+	f(&mut a.get_mut(), &mut b.get_mut())
+}
+
+/// Compares the contents of two singletons, by immutably borrowing both.
+///
+/// In debug builds, this will panic if either singleton is mutably accessed from a different thread or if a mutable reference to either is currently held.
+impl<T: PartialEq> PartialEq for Singleton<T> {
+	#[inline]
+	fn eq(&self, other: &Self) -> bool {
+		*self.0.get() == *other.0.get()
+	}
+}
+impl<T: Eq> Eq for Singleton<T> {}
+
+/// Hashes the contents of the singleton, by immutably borrowing it.
+///
+/// As with any type with interior mutability, a singleton which is used as the key of a map or set must not be mutated afterwards.
+///
+/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+impl<T: core::hash::Hash> core::hash::Hash for Singleton<T> {
+	#[inline]
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.0.get().hash(state)
 	}
 }
 
@@ -85,26 +883,32 @@ impl<T> Singleton<T> {
 ///
 /// In debug builds, usage of this abstraction is checked for safety at runtime.
 ///
-/// * Using this struct across threads will panic.
+/// * Using this struct across threads will panic, once a thread id provider has been set with `set_thread_id_provider`.
 /// * Mixing mutabilty of borrows will panic (this is bypassed if you are using the pointer getters)
 /// * Using this struct before initializing it will panic.
 /// * Initializing the value more than once will panic. Use `replace`
+///
+/// # Lifecycle
+///
+/// The singleton is always either **uninitialized** or **initialized**, and this state is tracked in every build:
+///
+/// * `uninit` constructs an uninitialized singleton, and `new` constructs an initialized one.
+/// * `init` (or `try_init`, `init_with`, etc.) moves it from uninitialized to initialized.
+/// * `deinit` drops the value, moving it from initialized back to uninitialized.
+/// * `replace` swaps the value, and requires and preserves the initialized state.
+///
+/// A singleton constructed with `new` can therefore be deinitialized and initialized again any number of times, exactly like one constructed with `uninit`.
 pub struct SingletonUninit<T> {
 	inner: SinglytonCell<MaybeUninit<T>>,
-
-	#[cfg(debug_assertions)]
-	initialized: UnsafeCell<bool>
+	initialized: AtomicBool
 }
-unsafe impl<T> Sync for SingletonUninit<T> {}
 
 impl<T> SingletonUninit<T> {
 	#[inline]
 	pub const fn uninit() -> Self {
 		Self {
 			inner: SinglytonCell::new(MaybeUninit::uninit()),
-
-			#[cfg(debug_assertions)]
-			initialized: UnsafeCell::new(false)
+			initialized: AtomicBool::new(false)
 		}
 	}
 
@@ -112,17 +916,20 @@ impl<T> SingletonUninit<T> {
 	pub const fn new(val: T) -> Self {
 		Self {
 			inner: SinglytonCell::new(MaybeUninit::new(val)),
-
-			#[cfg(debug_assertions)]
-			initialized: UnsafeCell::new(true)
+			initialized: AtomicBool::new(true)
 		}
 	}
 
+	#[inline]
+	fn is_initialized(&'static self) -> bool {
+		self.initialized.load(Ordering::Acquire)
+	}
+
 	#[cfg(debug_assertions)]
 	#[inline(never)]
 	fn uninit_check(&'static self) {
-		if !unsafe { *self.initialized.get() } {
-			panic!("This SingletonUninit has not been initialized yet");
+		if !self.is_initialized() {
+			violation::violation(ViolationKind::Uninitialized, self.inner.name());
 		}
 	}
 
@@ -131,88 +938,350 @@ impl<T> SingletonUninit<T> {
 	fn uninit_check(&'static self) {}
 
 	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
 	/// Assumes the memory is **initialized** and acquires an **immutable reference** to the singleton.
 	///
-	/// In debug builds, this will panic if the memory is not initialized, the singleton is mutably accessed from a different thread, or a mutable reference is currently held.
-	pub fn get(&'static self) -> SinglytonRef<T> {
-		self.uninit_check();
-		map_ref(self.inner.get(), |maybe_uninit| unsafe {
-			maybe_uninit.assume_init_ref()
-		})
+	/// In debug builds, this will panic if the memory is not initialized, the singleton is mutably accessed from a different thread, or a mutable reference is currently held.
+	pub fn get(&'static self) -> SinglytonRef<'static, T> {
+		self.uninit_check();
+		map_ref(self.inner.get(), |maybe_uninit| unsafe {
+			maybe_uninit.assume_init_ref()
+		})
+	}
+
+	#[inline]
+	/// Acquires an **immutable reference** to the singleton if the memory is initialized, or returns `None` instead of panicking if it is not.
+	///
+	/// This is useful for code which may run both before and after the singleton is initialized.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn try_get_opt(&'static self) -> Option<SinglytonRef<'static, T>> {
+		let maybe_uninit = self.inner.get();
+		if !self.is_initialized() {
+			return None;
+		}
+		Some(map_ref(maybe_uninit, |maybe_uninit| unsafe {
+			maybe_uninit.assume_init_ref()
+		}))
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **mutable reference** to the singleton.
+	///
+	/// In debug builds, this will panic if the memory is not initialized, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	pub fn get_mut(&'static self) -> SinglytonRefMut<'static, T> {
+		self.uninit_check();
+		map_ref_mut(self.inner.get_mut(), |maybe_uninit| unsafe {
+			maybe_uninit.assume_init_mut()
+		})
+	}
+
+	#[inline]
+	/// Acquires an **immutable pointer** to the singleton.
+	///
+	/// In debug builds, this will panic if the memory is not initialized, the singleton is mutably accessed from a different thread, or a mutable reference is currently held.
+	///
+	/// # Safety
+	///
+	/// This is unsafe because the returned pointer bypasses any future borrow checking.
+	pub unsafe fn as_ptr(&'static self) -> *const T {
+		self.uninit_check();
+		self.inner.get_mut().as_ptr()
+	}
+
+	#[inline]
+	/// Acquires a **mutable pointer** to the singleton.
+	///
+	/// In debug builds, this will panic if the memory is not initialized, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	///
+	/// # Safety
+	///
+	/// This is unsafe because the returned pointer bypasses any future borrow checking.
+	pub unsafe fn as_mut_ptr(&'static self) -> *mut T {
+		self.uninit_check();
+		self.inner.get_mut().as_mut_ptr()
+	}
+
+	#[inline]
+	/// Replaces the value in the singleton with anew, returning the previous value.
+	///
+	/// The borrow is released before the previous value is returned, so its `Drop` implementation may access the singleton.
+	///
+	/// Panics if the memory is not initialized, as there is no previous value.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn replace(&'static self, val: T) -> T {
+		let mut maybe_uninit = self.inner.get_mut();
+		if !self.is_initialized() {
+			violation::violation(ViolationKind::Uninitialized, self.inner.name());
+		}
+
+		unsafe { core::mem::replace(&mut *maybe_uninit, MaybeUninit::new(val)).assume_init() }
+	}
+
+	#[inline]
+	#[cfg(feature = "test-util")]
+	/// Forgets which thread the singleton belongs to, so that the next thread to borrow it becomes its owner.
+	///
+	/// This is a test utility for harnesses which reuse the same static across tests that each run on a fresh thread. It has no effect unless a thread id provider has been set with `set_thread_id_provider`, and in release builds or with the `sync` or `spin` features.
+	pub fn reset_thread(&'static self) {
+		self.inner.reset_thread()
+	}
+
+	#[inline]
+	/// Drops the value in the singleton, returning it to uninitialized memory.
+	///
+	/// The singleton can be initialized again afterwards with `init`.
+	///
+	/// Panics if the memory is not initialized, as there is no value to drop.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn deinit(&'static self) {
+		let mut maybe_uninit = self.inner.get_mut();
+		if !self.is_initialized() {
+			violation::violation(ViolationKind::Uninitialized, self.inner.name());
+		}
+
+		unsafe {
+			core::ptr::drop_in_place(maybe_uninit.as_mut_ptr());
+		}
+		self.initialized.store(false, Ordering::Release);
+	}
+
+	#[inline]
+	#[cfg(debug_assertions)]
+	/// Initializes the memory in the singleton.
+	///
+	/// In debug builds, this will panic if the memory is **already initialized**, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	pub fn init(&'static self, val: T) {
+		let mut maybe_uninit = self.inner.get_mut();
+		if self.is_initialized() {
+			violation::violation(ViolationKind::AlreadyInitialized, self.inner.name());
+		}
+
+		maybe_uninit.write(val);
+
+		self.initialized.store(true, Ordering::Release);
+	}
+
+	#[inline]
+	#[cfg(not(debug_assertions))]
+	/// Initializes the memory in the singleton.
+	///
+	/// In debug builds, this will panic if the memory is **already initialized**, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	pub fn init(&'static self, val: T) {
+		self.inner.get_mut().write(val);
+		self.initialized.store(true, Ordering::Release);
+	}
+
+	#[inline]
+	/// Initializes the memory in the singleton with the value derived from `seed` by `f`, such as a configuration parsed from a string.
+	///
+	/// The singleton is not borrowed while `f` is called. In debug builds, the singleton is checked before calling `f`, so no work is wasted if it is already initialized.
+	///
+	/// In debug builds, this will panic if the memory is **already initialized**, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	pub fn init_map<S, F>(&'static self, seed: S, f: F)
+	where
+		F: FnOnce(S) -> T
+	{
+		#[cfg(debug_assertions)]
+		if self.is_initialized() {
+			violation::violation(ViolationKind::AlreadyInitialized, self.inner.name());
+		}
+
+		self.init(f(seed));
+	}
+
+	#[inline]
+	/// Initializes the memory in the singleton with the value returned by `f`, which is only constructed at this point.
+	///
+	/// Unlike `get_or_init_reporting`, this is not idempotent: initializing the singleton twice is a bug, which is reported before `f` runs, so an expensive `T` is never constructed in vain.
+	///
+	/// Panics if the memory is **already initialized**, in every build, so that the previous value is never overwritten and leaked.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn init_with_fn<F>(&'static self, f: F)
+	where
+		F: FnOnce() -> T
+	{
+		if self.is_initialized() {
+			violation::violation(ViolationKind::AlreadyInitialized, self.inner.name());
+		}
+
+		let val = f();
+
+		// `f` may have initialized the singleton itself
+		let mut maybe_uninit = self.inner.get_mut();
+		if self.is_initialized() {
+			violation::violation(ViolationKind::AlreadyInitialized, self.inner.name());
+		}
+
+		maybe_uninit.write(val);
+
+		self.initialized.store(true, Ordering::Release);
+	}
+
+	#[inline]
+	#[cfg(feature = "bytemuck")]
+	/// Initializes the memory in the singleton to all-zeros, in place.
+	///
+	/// Unlike `init`, no `T` is constructed on the stack, which makes this suitable for large buffers.
+	///
+	/// In debug builds, this will panic if the memory is **already initialized**, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	pub fn init_zeroed(&'static self)
+	where
+		T: bytemuck::Zeroable
+	{
+		let mut maybe_uninit = self.inner.get_mut();
+		#[cfg(debug_assertions)]
+		if self.is_initialized() {
+			violation::violation(ViolationKind::AlreadyInitialized, self.inner.name());
+		}
+
+		unsafe { maybe_uninit.as_mut_ptr().write_bytes(0, 1) };
+
+		self.initialized.store(true, Ordering::Release);
+	}
+
+	#[inline]
+	/// Initializes the memory in the singleton in place, by calling `f` with the uninitialized memory.
+	///
+	/// Unlike `init`, no `T` is constructed on the stack, which makes this suitable for building large globals piece by piece.
+	///
+	/// In debug builds, this will panic if the memory is **already initialized**, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	///
+	/// # Safety
+	///
+	/// `f` must fully initialize the memory, as the singleton is considered initialized once it returns.
+	pub unsafe fn init_with<F>(&'static self, f: F)
+	where
+		F: FnOnce(&mut MaybeUninit<T>)
+	{
+		let mut maybe_uninit = self.inner.get_mut();
+		#[cfg(debug_assertions)]
+		if self.is_initialized() {
+			violation::violation(ViolationKind::AlreadyInitialized, self.inner.name());
+		}
+
+		f(&mut maybe_uninit);
+
+		self.initialized.store(true, Ordering::Release);
+	}
+
+	#[inline]
+	/// Acquires a **mutable pointer** to the uninitialized memory in the singleton, so that it can be initialized in place, such as by a C function that fills a struct.
+	///
+	/// Once the memory has been written to, call `assume_init` to mark the singleton as initialized.
+	///
+	/// In debug builds, this will panic if the memory is **already initialized**, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	///
+	/// # Safety
+	///
+	/// This is unsafe because the returned pointer bypasses any future borrow checking. The memory must be fully initialized before `assume_init` is called, and must not be written to through this pointer afterwards.
+	pub unsafe fn as_uninit_mut_ptr(&'static self) -> *mut MaybeUninit<T> {
+		let mut maybe_uninit = self.inner.get_mut();
+		#[cfg(debug_assertions)]
+		if self.is_initialized() {
+			violation::violation(ViolationKind::AlreadyInitialized, self.inner.name());
+		}
+
+		&mut *maybe_uninit as *mut MaybeUninit<T>
 	}
 
 	#[inline]
-	/// Acquires a **mutable reference** to the singleton.
+	/// Marks the memory in the singleton as initialized, after it was written to through `as_uninit_mut_ptr`.
 	///
-	/// In debug builds, this will panic if the memory is not initialized, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
-	pub fn get_mut(&'static self) -> SinglytonRefMut<T> {
-		self.uninit_check();
-		map_ref_mut(self.inner.get_mut(), |maybe_uninit| unsafe {
-			maybe_uninit.assume_init_mut()
-		})
+	/// In debug builds, this will panic if the memory is **already initialized**, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	///
+	/// # Safety
+	///
+	/// The memory must have been fully initialized.
+	pub unsafe fn assume_init(&'static self) {
+		let _guard = self.inner.get_mut();
+		#[cfg(debug_assertions)]
+		if self.is_initialized() {
+			violation::violation(ViolationKind::AlreadyInitialized, self.inner.name());
+		}
+
+		self.initialized.store(true, Ordering::Release);
 	}
 
 	#[inline]
-	/// Acquires an **immutable pointer** to the singleton.
+	/// Initializes the memory in the singleton if it is not already initialized.
 	///
-	/// In debug builds, this will panic if the memory is not initialized, the singleton is mutably accessed from a different thread, or a mutable reference is currently held.
+	/// Returns `Err(val)`, handing the value back, if the memory is **already initialized**.
 	///
-	/// This is unsafe because the returned pointer bypasses any future borrow checking.
-	pub unsafe fn as_ptr(&'static self) -> *const T {
-		self.uninit_check();
-		self.inner.get_mut().as_ptr()
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held, even if the memory is already initialized.
+	pub fn try_init(&'static self, val: T) -> Result<(), T> {
+		let mut maybe_uninit = self.inner.get_mut();
+		if self.is_initialized() {
+			return Err(val);
+		}
+
+		maybe_uninit.write(val);
+
+		self.initialized.store(true, Ordering::Release);
+		Ok(())
 	}
 
 	#[inline]
-	/// Acquires a **mutable pointer** to the singleton.
+	/// Initializes the memory in the singleton with the value returned by `f` if it is not already initialized, then acquires an **immutable reference** to the singleton.
 	///
-	/// In debug builds, this will panic if the memory is not initialized, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	/// Also returns whether the singleton was initialized by this call. `f` is only called if so.
 	///
-	/// This is unsafe because the returned pointer bypasses any future borrow checking.
-	pub unsafe fn as_mut_ptr(&'static self) -> *mut T {
-		self.uninit_check();
-		self.inner.get_mut().as_mut_ptr()
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or a mutable reference is currently held.
+	pub fn get_or_init_reporting<F>(&'static self, f: F) -> (SinglytonRef<'static, T>, bool)
+	where
+		F: FnOnce() -> T
+	{
+		let initializing = !self.is_initialized();
+		if initializing {
+			self.init(f());
+		}
+		(self.get(), initializing)
 	}
 
 	#[inline]
-	/// Replaces the value in the singleton with anew.
+	/// Initializes the memory in the singleton with the value returned by `f` if it is not already initialized, then acquires an **immutable reference** to the singleton.
 	///
-	/// In debug builds, this will panic if the memory is not initialized, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
-	pub fn replace(&'static self, val: T) {
-		self.uninit_check();
-		unsafe {
-			let mut maybe_uninit = self.inner.get_mut();
-
-			core::ptr::drop_in_place(maybe_uninit.as_mut_ptr());
-			maybe_uninit.write(val);
+	/// If `f` returns `Err`, the error is returned and the singleton is left uninitialized, so initialization can be retried. `f` is not called if the singleton is already initialized.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or a mutable reference is currently held.
+	pub fn get_or_try_init<E, F>(&'static self, f: F) -> Result<SinglytonRef<'static, T>, E>
+	where
+		F: FnOnce() -> Result<T, E>
+	{
+		if !self.is_initialized() {
+			self.init(f()?);
 		}
+		Ok(self.get())
 	}
+}
 
+#[cfg(feature = "alloc")]
+impl<U: ?Sized> SingletonUninit<alloc::rc::Rc<U>> {
 	#[inline]
-	#[cfg(debug_assertions)]
-	/// Initializes the memory in the singleton.
+	/// Clones the `Rc` in the singleton, releasing the borrow before returning.
 	///
-	/// In debug builds, this will panic if the memory is **already initialized**, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
-	pub fn init(&'static self, val: T) {
-		unsafe {
-			let ref mut initialized = *self.initialized.get();
-			if *initialized {
-				panic!("This SingletonUninit has already been initialized");
-			}
-
-			self.inner.get_mut().write(val);
-
-			*initialized = true;
-		}
+	/// This gives the caller their own owning handle to the shared value, so no guard needs to be held while using it.
+	///
+	/// In debug builds, this will panic if the memory is not initialized, the singleton is mutably accessed from a different thread, or a mutable reference is currently held.
+	pub fn get_rc(&'static self) -> alloc::rc::Rc<U> {
+		self.get().clone()
 	}
+}
 
+#[cfg(feature = "alloc")]
+impl<U: ?Sized> SingletonUninit<alloc::sync::Arc<U>> {
 	#[inline]
-	#[cfg(not(debug_assertions))]
-	/// Initializes the memory in the singleton.
+	/// Clones the `Arc` in the singleton, releasing the borrow before returning.
 	///
-	/// In debug builds, this will panic if the memory is **already initialized**, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
-	pub fn init(&'static self, val: T) {
-		self.inner.get_mut().write(val);
+	/// This gives the caller their own owning handle to the shared value, so no guard needs to be held while using it.
+	///
+	/// In debug builds, this will panic if the memory is not initialized, the singleton is mutably accessed from a different thread, or a mutable reference is currently held.
+	pub fn get_arc(&'static self) -> alloc::sync::Arc<U> {
+		self.get().clone()
 	}
 }
 
@@ -226,12 +1295,26 @@ impl<T> SingletonUninit<T> {
 ///
 /// In debug builds, usage of this abstraction is checked for safety at runtime.
 ///
-/// * Using this struct across threads will panic.
+/// * Using this struct across threads will panic, once a thread id provider has been set with `set_thread_id_provider`.
 /// * Mixing mutabilty of borrows will panic (this is bypassed if you are using the pointer getters)
 #[repr(transparent)]
 pub struct SingletonOption<T>(SinglytonCell<Option<T>>);
-unsafe impl<T> Sync for SingletonOption<T> {}
 
+impl<T> Default for SingletonOption<T> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+/// Extends the collection in the singleton, inserting `C::default()` first if it is `None`.
+///
+/// The singleton is mutably borrowed while the iterator is consumed, so in debug builds, this will panic if the iterator accesses the singleton itself.
+impl<C: Extend<A> + Default, A> Extend<A> for &'static SingletonOption<C> {
+	#[inline]
+	fn extend<I: IntoIterator<Item = A>>(&mut self, iter: I) {
+		self.get_or_insert_default().extend(iter);
+	}
+}
 impl<T> SingletonOption<T> {
 	#[inline]
 	pub const fn new() -> Self {
@@ -239,23 +1322,50 @@ impl<T> SingletonOption<T> {
 	}
 
 	#[inline]
+	/// Creates a singleton which is initially `Some(val)`.
+	///
+	/// This is a `const fn` for any `T`, including types which are not `Copy` or which implement `Drop`, so it can be used in a `static` as long as `val` itself can be built in a const context:
+	///
+	/// ```
+	/// # use singlyton::SingletonOption;
+	/// static NAMES: SingletonOption<Vec<String>> = SingletonOption::new_some(Vec::new());
+	/// static GREETING: SingletonOption<String> = SingletonOption::new_some(String::new());
+	/// ```
+	///
+	/// Values which can only be built at runtime, such as a `String` with contents, are rejected by the compiler. Use `SingletonOption::new` and `replace` them in at runtime instead.
+	///
+	/// ```compile_fail
+	/// # use singlyton::SingletonOption;
+	/// static GREETING: SingletonOption<String> = SingletonOption::new_some(String::from("Hello"));
+	/// ```
 	pub const fn new_some(val: T) -> Self {
 		Self(SinglytonCell::new(Some(val)))
 	}
 
 	#[inline]
+	#[cfg(feature = "test-util")]
+	/// Forgets which thread the singleton belongs to, so that the next thread to borrow it becomes its owner.
+	///
+	/// This is a test utility for harnesses which reuse the same static across tests that each run on a fresh thread. It has no effect unless a thread id provider has been set with `set_thread_id_provider`, and in release builds or with the `sync` or `spin` features.
+	pub fn reset_thread(&'static self) {
+		self.0.reset_thread()
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
 	/// Acquires an **immutable reference** to the inner `Option<T>`.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
-	pub fn as_option(&'static self) -> SinglytonRef<Option<T>> {
+	pub fn as_option(&'static self) -> SinglytonRef<'static, Option<T>> {
 		self.0.get()
 	}
 
 	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
 	/// Acquires a **mutable reference** to the inner `Option<T>`.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
-	pub fn as_option_mut(&'static self) -> SinglytonRefMut<Option<T>> {
+	pub fn as_option_mut(&'static self) -> SinglytonRefMut<'static, Option<T>> {
 		self.0.get_mut()
 	}
 
@@ -264,9 +1374,11 @@ impl<T> SingletonOption<T> {
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
 	///
+	/// # Safety
+	///
 	/// This is unsafe because the returned pointer bypasses any future borrow checking.
 	pub unsafe fn as_option_ptr(&'static self) -> *const Option<T> {
-		&*self.0.get_unchecked() as *const Option<T>
+		self.0.get_unchecked() as *const Option<T>
 	}
 
 	#[inline]
@@ -274,37 +1386,171 @@ impl<T> SingletonOption<T> {
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
 	///
+	/// # Safety
+	///
 	/// This is unsafe because the returned pointer bypasses any future borrow checking.
 	pub unsafe fn as_option_mut_ptr(&'static self) -> *mut Option<T> {
-		&mut *self.0.get_mut_unchecked() as *mut Option<T>
+		self.0.get_mut_unchecked() as *mut Option<T>
 	}
 
 	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
 	/// Acquires an **immutable reference** to the singleton.
 	///
 	/// Panics if the singleton is `None`.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
-	pub fn get(&'static self) -> SinglytonRef<T> {
+	pub fn get(&'static self) -> SinglytonRef<'static, T> {
 		map_ref(self.0.get(), |opt| opt.as_ref().unwrap())
 	}
 
 	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
 	/// Acquires a **mutable reference** to the singleton.
 	///
 	/// Panics if the singleton is `None`.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
-	pub fn get_mut(&'static self) -> SinglytonRefMut<T> {
+	pub fn get_mut(&'static self) -> SinglytonRefMut<'static, T> {
 		map_ref_mut(self.0.get_mut(), |opt| opt.as_mut().unwrap())
 	}
 
 	#[inline]
-	/// Replaces the value in the singleton with anew.
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires an **immutable reference** to the singleton, assuming it is `Some(T)`.
+	///
+	/// This is a fast path for hot reads of a singleton which is known to be set: in release builds, the `None` check is skipped entirely (see also `SingletonOptionUnchecked`). In debug builds, this still panics if the singleton is `None`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	///
+	/// # Safety
+	///
+	/// In release builds, the singleton must be `Some(T)`, otherwise this is undefined behaviour.
+	pub unsafe fn get_unchecked(&'static self) -> SinglytonRef<'static, T> {
+		map_ref(self.0.get(), |opt| {
+			#[cfg(debug_assertions)] {
+				opt.as_ref().unwrap()
+			}
+			#[cfg(not(debug_assertions))] {
+				opt.as_ref().unwrap_unchecked()
+			}
+		})
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **mutable reference** to the singleton, assuming it is `Some(T)`.
+	///
+	/// This is a fast path for hot writes to a singleton which is known to be set: in release builds, the `None` check is skipped entirely (see also `SingletonOptionUnchecked`). In debug builds, this still panics if the singleton is `None`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	///
+	/// # Safety
+	///
+	/// In release builds, the singleton must be `Some(T)`, otherwise this is undefined behaviour.
+	pub unsafe fn get_mut_unchecked(&'static self) -> SinglytonRefMut<'static, T> {
+		map_ref_mut(self.0.get_mut(), |opt| {
+			#[cfg(debug_assertions)] {
+				opt.as_mut().unwrap()
+			}
+			#[cfg(not(debug_assertions))] {
+				opt.as_mut().unwrap_unchecked()
+			}
+		})
+	}
+
+	#[inline]
+	/// Acquires an **immutable pointer** to the value inside the singleton.
+	///
+	/// Panics if the singleton is `None`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	///
+	/// # Safety
+	///
+	/// This is unsafe because the returned pointer bypasses any future borrow checking. The pointer dangles once the singleton is set to `None` or replaced.
+	pub unsafe fn as_ptr(&'static self) -> *const T {
+		&*self.get() as *const T
+	}
+
+	#[inline]
+	/// Acquires a **mutable pointer** to the value inside the singleton.
+	///
+	/// Panics if the singleton is `None`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	///
+	/// # Safety
+	///
+	/// This is unsafe because the returned pointer bypasses any future borrow checking. The pointer dangles once the singleton is set to `None` or replaced.
+	pub unsafe fn as_mut_ptr(&'static self) -> *mut T {
+		&mut *self.get_mut() as *mut T
+	}
+
+	#[inline]
+	/// Acquires a **mutable reference** to the singleton, inserting `T::default()` first if it is `None`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn get_or_insert_default(&'static self) -> SinglytonRefMut<'static, T>
+	where
+		T: Default
+	{
+		map_ref_mut(self.0.get_mut(), |opt| opt.get_or_insert_with(T::default))
+	}
+
+	#[inline]
+	/// Acquires a **mutable reference** to the singleton, inserting `default` first if it is `None`.
+	///
+	/// `default` is dropped if the singleton is already `Some(T)`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn get_mut_or(&'static self, default: T) -> SinglytonRefMut<'static, T> {
+		map_ref_mut(self.0.get_mut(), |opt| opt.get_or_insert(default))
+	}
+
+	#[inline]
+	/// Acquires a **mutable reference** to the singleton, inserting the value returned by `f` first if it is `None`.
+	///
+	/// `f` is only called if the singleton is `None`. If it returns an error, the singleton is left as `None` so that initialization can be retried later.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn get_or_try_insert_with<E, F>(&'static self, f: F) -> Result<SinglytonRefMut<'static, T>, E>
+	where
+		F: FnOnce() -> Result<T, E>
+	{
+		let mut opt = self.0.get_mut();
+		if opt.is_none() {
+			*opt = Some(f()?);
+		}
+		Ok(map_ref_mut(opt, |opt| opt.as_mut().unwrap()))
+	}
+
+	#[inline]
+	/// Acquires a **mutable reference** to the singleton, inserting the value returned by `f` first if it is `None`.
+	///
+	/// Also returns whether the value was inserted by this call, which is useful for performing first-time registration exactly once. `f` is only called if so.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn get_or_insert_reporting<F>(&'static self, f: F) -> (SinglytonRefMut<'static, T>, bool)
+	where
+		F: FnOnce() -> T
+	{
+		let mut opt = self.0.get_mut();
+		let inserting = opt.is_none();
+		if inserting {
+			*opt = Some(f());
+		}
+		(map_ref_mut(opt, |opt| opt.as_mut().unwrap()), inserting)
+	}
+
+	#[inline]
+	/// Replaces the value in the singleton with anew, returning the previous value (if any).
+	///
+	/// The borrow is released before the previous value is returned, so its `Drop` implementation may access the singleton. This makes it suitable for rotating a global resource, disposing of the previous one outside of the borrow.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
-	pub fn replace(&'static self, val: T) {
-		self.0.get_mut().replace(val);
+	pub fn replace(&'static self, val: T) -> Option<T> {
+		self.0.get_mut().replace(val)
 	}
 
 	#[inline]
@@ -315,6 +1561,117 @@ impl<T> SingletonOption<T> {
 		self.0.get_mut().take()
 	}
 
+	#[inline]
+	/// Sets the singleton to `Some(val)`, dropping the previous value (if any).
+	///
+	/// Like `replace`, but drops the previous value instead of returning it. The borrow is released before the previous value is dropped, so its `Drop` implementation may access the singleton.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn set(&'static self, val: T) {
+		drop(self.replace(val));
+	}
+
+	#[inline]
+	/// Sets the singleton to `Some(val)` only if it is `None`.
+	///
+	/// Returns `Err(val)`, handing the value back rather than dropping it, if the singleton is already `Some(T)`. Unlike `get_mut_or`, no reference is acquired, which makes this suitable for set-once globals.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn replace_if_none(&'static self, val: T) -> Result<(), T> {
+		let mut opt = self.0.get_mut();
+		if opt.is_some() {
+			return Err(val);
+		}
+		*opt = Some(val);
+		Ok(())
+	}
+
+	#[inline]
+	/// Sets the singleton to `None`, dropping the previous value (if any).
+	///
+	/// This is equivalent to discarding the result of `take`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn unset(&'static self) {
+		drop(self.take());
+	}
+
+	#[inline]
+	/// Takes the value out of the singleton, or returns `default` if it is `None`, leaving the singleton `None` either way.
+	///
+	/// `default` is dropped if the singleton is `Some(T)`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn take_or(&'static self, default: T) -> T {
+		self.take().unwrap_or(default)
+	}
+
+	#[inline]
+	/// Calls `f` with an **immutable reference** to the value inside the singleton if it is `Some(T)`, then returns the singleton for chaining.
+	///
+	/// The borrow is released before returning.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn inspect<F>(&'static self, f: F) -> &'static Self
+	where
+		F: FnOnce(&T)
+	{
+		if let Some(val) = &*self.0.get() {
+			f(val);
+		}
+		self
+	}
+
+	#[inline]
+	/// Calls `f` with a **mutable reference** to the value inside the singleton if it is `Some(T)`, then returns the singleton for chaining.
+	///
+	/// The borrow is released before returning.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn inspect_mut<F>(&'static self, f: F) -> &'static Self
+	where
+		F: FnOnce(&mut T)
+	{
+		if let Some(val) = &mut *self.0.get_mut() {
+			f(val);
+		}
+		self
+	}
+
+	#[inline]
+	/// Calls `f` with an **immutable reference** to the value inside the singleton if it is `Some(T)`, or calls `default` if it is `None`, and returns the result.
+	///
+	/// Unlike `get`, this never panics if the singleton is `None`. The borrow is released before `default` is called and before returning.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn map_or_else<U, D, F>(&'static self, default: D, f: F) -> U
+	where
+		D: FnOnce() -> U,
+		F: FnOnce(&T) -> U
+	{
+		let opt = self.0.get();
+		if let Some(val) = &*opt {
+			return f(val);
+		}
+		drop(opt);
+		default()
+	}
+
+	#[inline]
+	/// Calls `f` with an **immutable reference** to the value inside the singleton and returns its result, such as a tuple of several projected fields.
+	///
+	/// Panics if the singleton is `None`.
+	///
+	/// The borrow is released before returning.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn get_fields<R, F>(&'static self, f: F) -> R
+	where
+		F: FnOnce(&T) -> R
+	{
+		f(&self.get())
+	}
+
 	#[inline]
 	/// Tests if the singleton is `Some(T)`.
 	///
@@ -332,6 +1689,79 @@ impl<T> SingletonOption<T> {
 	}
 }
 
+impl<T> SingletonOption<Option<T>> {
+	#[inline]
+	/// Acquires an **immutable reference** to the value inside the nested `Option<T>` if both it and the singleton are `Some`, or returns `None` otherwise.
+	///
+	/// Unlike `get`, this never panics if the singleton is `None`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn flatten_get(&'static self) -> Option<SinglytonRef<'static, T>> {
+		let opt = self.0.get();
+		if let Some(Some(_)) = &*opt {
+			Some(map_ref(opt, |opt| opt.as_ref().and_then(Option::as_ref).unwrap()))
+		} else {
+			None
+		}
+	}
+}
+
+impl<T: core::ops::Deref<Target = [U]>, U> SingletonOption<T> {
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires an **immutable reference** to the contents of the singleton as a slice, such as for an `Option<Vec<U>>`.
+	///
+	/// Panics if the singleton is `None`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn as_slice(&'static self) -> SinglytonRef<'static, [U]> {
+		map_ref(self.get(), |val| &**val)
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **mutable reference** to the contents of the singleton as a slice, such as for an `Option<Vec<U>>`.
+	///
+	/// Panics if the singleton is `None`.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn as_mut_slice(&'static self) -> SinglytonRefMut<'static, [U]>
+	where
+		T: core::ops::DerefMut
+	{
+		map_ref_mut(self.get_mut(), |val| &mut **val)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<T> SingletonOption<Vec<T>> {
+	#[inline]
+	/// Takes the vector out of the singleton, leaving `None` in its place, and returns an iterator over its elements.
+	///
+	/// The borrow is released before returning, so the singleton can be accessed again while iterating. If the singleton is `None`, the iterator is empty.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn take_iter(&'static self) -> impl Iterator<Item = T> {
+		self.take().into_iter().flatten()
+	}
+}
+
+#[cfg(feature = "std")]
+impl<K: Eq + core::hash::Hash, V, S: core::hash::BuildHasher + Default> SingletonOption<std::collections::HashMap<K, V, S>> {
+	#[inline]
+	/// Acquires a **mutable reference** to the entry for `key` in the map, inserting an empty map first if the singleton is `None` and the value returned by `f` if the entry is vacant.
+	///
+	/// `f` is only called if the entry is vacant.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn entry_or_insert_with<F>(&'static self, key: K, f: F) -> SinglytonRefMut<'static, V>
+	where
+		F: FnOnce() -> V
+	{
+		map_ref_mut(self.0.get_mut(), |map| map.get_or_insert_with(Default::default).entry(key).or_insert_with(f))
+	}
+}
+
 /// A **thread-unsafe** global singleton containg an `Option<T>`.
 ///
 /// All operations (except `as_option` and `as_option_mut`) automatically unwrap **without checking if the Option<T> is Some(T) in release builds** and will lead to undefined behaviour otherwise.
@@ -342,12 +1772,17 @@ impl<T> SingletonOption<T> {
 ///
 /// In debug builds, usage of this abstraction is checked for safety at runtime.
 ///
-/// * Using this struct across threads will panic.
+/// * Using this struct across threads will panic, once a thread id provider has been set with `set_thread_id_provider`.
 /// * Mixing mutabilty of borrows will panic (this is bypassed if you are using the pointer getters)
 #[repr(transparent)]
 pub struct SingletonOptionUnchecked<T>(SinglytonCell<Option<T>>);
-unsafe impl<T> Sync for SingletonOptionUnchecked<T> {}
 
+impl<T> Default for SingletonOptionUnchecked<T> {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
 impl<T> SingletonOptionUnchecked<T> {
 	#[inline]
 	pub const fn new() -> Self {
@@ -360,18 +1795,20 @@ impl<T> SingletonOptionUnchecked<T> {
 	}
 
 	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
 	/// Acquires an **immutable reference** to the inner `Option<T>`.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
-	pub fn as_option(&'static self) -> SinglytonRef<Option<T>> {
+	pub fn as_option(&'static self) -> SinglytonRef<'static, Option<T>> {
 		self.0.get()
 	}
 
 	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
 	/// Acquires a **mutable reference** to the inner `Option<T>`.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
-	pub fn as_option_mut(&'static self) -> SinglytonRefMut<Option<T>> {
+	pub fn as_option_mut(&'static self) -> SinglytonRefMut<'static, Option<T>> {
 		self.0.get_mut()
 	}
 
@@ -380,9 +1817,11 @@ impl<T> SingletonOptionUnchecked<T> {
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
 	///
+	/// # Safety
+	///
 	/// This is unsafe because the returned pointer bypasses any future borrow checking.
 	pub unsafe fn as_option_ptr(&'static self) -> *const Option<T> {
-		&*self.0.get_unchecked() as *const Option<T>
+		self.0.get_unchecked() as *const Option<T>
 	}
 
 	#[inline]
@@ -390,18 +1829,25 @@ impl<T> SingletonOptionUnchecked<T> {
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
 	///
+	/// # Safety
+	///
 	/// This is unsafe because the returned pointer bypasses any future borrow checking.
 	pub unsafe fn as_option_mut_ptr(&'static self) -> *mut Option<T> {
-		&mut *self.0.get_mut_unchecked() as *mut Option<T>
+		self.0.get_mut_unchecked() as *mut Option<T>
 	}
 
 	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
 	/// Acquires an **immutable reference** to the singleton.
 	///
 	/// Panics if the singleton is `None`.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
-	pub unsafe fn get(&'static self) -> SinglytonRef<T> {
+	///
+	/// # Safety
+	///
+	/// In release builds, the singleton must be `Some(T)`, otherwise this is undefined behaviour.
+	pub unsafe fn get(&'static self) -> SinglytonRef<'static, T> {
 		map_ref(self.0.get(), |opt| {
 			#[cfg(debug_assertions)] {
 				opt.as_ref().unwrap()
@@ -413,12 +1859,17 @@ impl<T> SingletonOptionUnchecked<T> {
 	}
 
 	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
 	/// Acquires a **mutable reference** to the singleton.
 	///
 	/// Panics if the singleton is `None`.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
-	pub unsafe fn get_mut(&'static self) -> SinglytonRefMut<T> {
+	///
+	/// # Safety
+	///
+	/// In release builds, the singleton must be `Some(T)`, otherwise this is undefined behaviour.
+	pub unsafe fn get_mut(&'static self) -> SinglytonRefMut<'static, T> {
 		map_ref_mut(self.0.get_mut(), |opt| {
 			#[cfg(debug_assertions)] {
 				opt.as_mut().unwrap()
@@ -430,11 +1881,13 @@ impl<T> SingletonOptionUnchecked<T> {
 	}
 
 	#[inline]
-	/// Replaces the value in the singleton with anew.
+	/// Replaces the value in the singleton with anew, returning the previous value (if any).
+	///
+	/// The borrow is released before the previous value is returned, so its `Drop` implementation may access the singleton.
 	///
 	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
-	pub fn replace(&'static self, val: T) {
-		self.0.get_mut().replace(val);
+	pub fn replace(&'static self, val: T) -> Option<T> {
+		self.0.get_mut().replace(val)
 	}
 
 	#[inline]
@@ -0,0 +1,476 @@
+#[cfg(any(test, all(not(feature = "sync"), any(debug_assertions, feature = "spin"))))]
+use core::sync::atomic::{AtomicIsize, AtomicUsize, Ordering};
+#[cfg(any(test, all(not(feature = "sync"), any(debug_assertions, feature = "spin"))))]
+use crate::violation::{violation, ViolationKind};
+#[cfg(all(feature = "backtrace", any(test, all(not(feature = "sync"), any(debug_assertions, feature = "spin")))))]
+use crate::violation::violation_at;
+
+/// A lock guarding the contents of a `SinglytonCell`.
+///
+/// Every singleton is backed by exactly one implementation of this trait, selected by the build profile and features (see `Lock`). The borrow guards and everything built on top of them are shared between all implementations.
+pub(crate) trait BorrowLock {
+	type Borrow<'a> where Self: 'a;
+	type BorrowMut<'a> where Self: 'a;
+	type UpgradableBorrow<'a> where Self: 'a;
+
+	const INIT: Self;
+
+	/// Acquires an immutable borrow, panicking or blocking if it is mutably borrowed.
+	///
+	/// `name` is the name of the singleton to report if this panics.
+	fn borrow(&self, name: Option<&'static str>) -> Self::Borrow<'_>;
+
+	/// Acquires a mutable borrow, panicking or blocking if it is borrowed.
+	///
+	/// `name` is the name of the singleton to report if this panics.
+	fn borrow_mut(&self, name: Option<&'static str>) -> Self::BorrowMut<'_>;
+
+	/// Acquires an immutable borrow which can later be upgraded to a mutable borrow with `upgrade`, panicking or blocking if it is mutably borrowed.
+	///
+	/// Locks which cannot upgrade a shared borrow atomically acquire a mutable borrow here instead, so that nothing can change the value before the upgrade.
+	///
+	/// `name` is the name of the singleton to report if this panics.
+	fn borrow_upgradable(&self, name: Option<&'static str>) -> Self::UpgradableBorrow<'_>;
+
+	/// Upgrades an upgradable borrow to a mutable borrow, without releasing it in between.
+	///
+	/// `name` is the name of the singleton to report if this panics.
+	fn upgrade<'a>(&'a self, borrow: Self::UpgradableBorrow<'a>, name: Option<&'static str>) -> Self::BorrowMut<'a>;
+
+	/// Acquires an immutable borrow, or returns `None` if it is mutably borrowed.
+	fn try_borrow(&self) -> Option<Self::Borrow<'_>>;
+
+	/// Acquires a mutable borrow, or returns `None` if it is borrowed.
+	fn try_borrow_mut(&self) -> Option<Self::BorrowMut<'_>>;
+
+	/// Splits a mutable borrow in two. The lock stays mutably borrowed until both are dropped.
+	fn split<'a>(borrow: Self::BorrowMut<'a>) -> (Self::BorrowMut<'a>, Self::BorrowMut<'a>)
+	where
+		Self: 'a;
+
+	/// The number of live immutable borrows, or zero if this lock does not count them.
+	#[inline]
+	fn immutable_borrow_count(&self) -> usize {
+		0
+	}
+
+	/// Whether a mutable borrow is live, or `false` if this lock does not track it.
+	#[inline]
+	fn has_mutable_borrow(&self) -> bool {
+		false
+	}
+
+	/// Forgets which thread this belongs to, if this lock checks thread ids.
+	#[inline]
+	#[cfg(feature = "test-util")]
+	fn reset_thread(&self) {}
+}
+
+#[cfg(feature = "sync")]
+pub(crate) type Lock = std::sync::RwLock<()>;
+#[cfg(all(feature = "spin", not(feature = "sync")))]
+pub(crate) type Lock = SpinLock;
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin"))))]
+pub(crate) type Lock = BorrowFlag;
+#[cfg(all(not(debug_assertions), not(any(feature = "sync", feature = "spin"))))]
+pub(crate) type Lock = Unchecked;
+
+/// Performs no checks at all. Used in release builds.
+#[cfg(any(test, all(not(debug_assertions), not(any(feature = "sync", feature = "spin")))))]
+pub(crate) struct Unchecked;
+#[cfg(any(test, all(not(debug_assertions), not(any(feature = "sync", feature = "spin")))))]
+impl BorrowLock for Unchecked {
+	type Borrow<'a> = ();
+	type BorrowMut<'a> = ();
+	type UpgradableBorrow<'a> = ();
+
+	const INIT: Self = Unchecked;
+
+	#[inline]
+	fn borrow(&self, _: Option<&'static str>) {}
+
+	#[inline]
+	fn borrow_mut(&self, _: Option<&'static str>) {}
+
+	#[inline]
+	fn borrow_upgradable(&self, _: Option<&'static str>) {}
+
+	#[inline]
+	fn upgrade(&self, _: (), _: Option<&'static str>) {}
+
+	#[inline]
+	fn try_borrow(&self) -> Option<()> {
+		Some(())
+	}
+
+	#[inline]
+	fn try_borrow_mut(&self) -> Option<()> {
+		Some(())
+	}
+
+	#[inline]
+	fn split<'a>(_: ()) -> ((), ())
+	where
+		Self: 'a
+	{
+		((), ())
+	}
+}
+
+/// Panics on conflicting borrows, like `RefCell`. Used in debug builds.
+///
+/// A positive count is the number of immutable borrows and a negative count is the number of mutable borrows.
+/// There can only be more than one mutable borrow if a `SinglytonRefMut` has been split.
+///
+/// If a thread id provider has been set, this also panics if it is borrowed from a different thread than the first one to borrow it.
+///
+/// With the `backtrace` feature, this also records where the live borrows were acquired, to report in the panic.
+#[cfg(any(test, all(not(feature = "sync"), any(debug_assertions, feature = "spin"))))]
+pub(crate) struct BorrowFlag {
+	count: AtomicIsize,
+	// The id of the thread this belongs to plus one, or zero if it does not belong to a thread yet
+	owner: AtomicUsize,
+	// Where the oldest live borrow was acquired (a leaked `Box`), which is forgotten once every borrow is released
+	// This is not a `Mutex`, so that singletons have no drop glue and can still be used in const contexts
+	#[cfg(feature = "backtrace")]
+	origin: core::sync::atomic::AtomicPtr<std::backtrace::Backtrace>
+}
+#[cfg(any(test, all(not(feature = "sync"), any(debug_assertions, feature = "spin"))))]
+impl BorrowFlag {
+	#[inline]
+	#[track_caller]
+	fn check_thread(&self, name: Option<&'static str>) {
+		let Some(thread) = crate::thread::current() else { return };
+		let thread = thread.wrapping_add(1);
+		match self.owner.compare_exchange(0, thread, Ordering::Relaxed, Ordering::Relaxed) {
+			Ok(_) => {},
+			Err(owner) if owner == thread => {},
+			Err(_) => violation(ViolationKind::WrongThread, name)
+		}
+	}
+
+	#[inline]
+	fn acquire(&self) -> Option<BorrowRef<'_>> {
+		let mut count = self.count.load(Ordering::Relaxed);
+		loop {
+			if count < 0 {
+				return None;
+			}
+			match self.count.compare_exchange_weak(count, count + 1, Ordering::Acquire, Ordering::Relaxed) {
+				Ok(_) => return Some(BorrowRef(self)),
+				Err(actual) => count = actual
+			}
+		}
+	}
+
+	#[inline]
+	fn acquire_mut(&self) -> Option<BorrowRefMut<'_>> {
+		match self.count.compare_exchange(0, -1, Ordering::Acquire, Ordering::Relaxed) {
+			Ok(_) => Some(BorrowRefMut(self)),
+			Err(_) => None
+		}
+	}
+
+	#[inline]
+	#[cfg(feature = "backtrace")]
+	fn take_origin(&self) -> Option<std::boxed::Box<std::backtrace::Backtrace>> {
+		let origin = self.origin.swap(core::ptr::null_mut(), Ordering::AcqRel);
+		(!origin.is_null()).then(|| unsafe { std::boxed::Box::from_raw(origin) })
+	}
+
+	#[inline]
+	fn record_origin(&self) {
+		#[cfg(feature = "backtrace")]
+		if self.origin.load(Ordering::Acquire).is_null() {
+			let origin = std::boxed::Box::into_raw(std::boxed::Box::new(std::backtrace::Backtrace::force_capture()));
+			if self.origin.compare_exchange(core::ptr::null_mut(), origin, Ordering::AcqRel, Ordering::Acquire).is_err() {
+				drop(unsafe { std::boxed::Box::from_raw(origin) });
+			}
+		}
+	}
+
+	#[inline]
+	fn forget_origin(&self) {
+		#[cfg(feature = "backtrace")]
+		drop(self.take_origin());
+	}
+
+	#[cold]
+	#[track_caller]
+	fn conflict(&self, kind: ViolationKind, name: Option<&'static str>) -> ! {
+		#[cfg(feature = "backtrace")] {
+			violation_at(kind, name, self.take_origin().map(std::sync::Arc::from))
+		}
+		#[cfg(not(feature = "backtrace"))] {
+			violation(kind, name)
+		}
+	}
+}
+
+#[cfg(any(test, all(not(feature = "sync"), any(debug_assertions, feature = "spin"))))]
+pub(crate) struct BorrowRef<'a>(&'a BorrowFlag);
+#[cfg(any(test, all(not(feature = "sync"), any(debug_assertions, feature = "spin"))))]
+impl Drop for BorrowRef<'_> {
+	#[inline]
+	fn drop(&mut self) {
+		if self.0.count.fetch_sub(1, Ordering::Release) == 1 {
+			self.0.forget_origin();
+		}
+	}
+}
+
+#[cfg(any(test, all(not(feature = "sync"), any(debug_assertions, feature = "spin"))))]
+pub(crate) struct BorrowRefMut<'a>(&'a BorrowFlag);
+#[cfg(any(test, all(not(feature = "sync"), any(debug_assertions, feature = "spin"))))]
+impl Drop for BorrowRefMut<'_> {
+	#[inline]
+	fn drop(&mut self) {
+		if self.0.count.fetch_add(1, Ordering::Release) == -1 {
+			self.0.forget_origin();
+		}
+	}
+}
+
+#[cfg(any(test, all(not(feature = "sync"), any(debug_assertions, feature = "spin"))))]
+impl BorrowLock for BorrowFlag {
+	type Borrow<'a> = BorrowRef<'a>;
+	type BorrowMut<'a> = BorrowRefMut<'a>;
+	type UpgradableBorrow<'a> = BorrowRef<'a>;
+
+	const INIT: Self = BorrowFlag {
+		count: AtomicIsize::new(0),
+		owner: AtomicUsize::new(0),
+		#[cfg(feature = "backtrace")]
+		origin: core::sync::atomic::AtomicPtr::new(core::ptr::null_mut())
+	};
+
+	#[inline]
+	#[track_caller]
+	fn borrow(&self, name: Option<&'static str>) -> BorrowRef<'_> {
+		self.check_thread(name);
+		match self.acquire() {
+			Some(borrow) => {
+				self.record_origin();
+				borrow
+			},
+			None => self.conflict(ViolationKind::AlreadyMutablyBorrowed, name)
+		}
+	}
+
+	#[inline]
+	#[track_caller]
+	fn borrow_mut(&self, name: Option<&'static str>) -> BorrowRefMut<'_> {
+		self.check_thread(name);
+		match self.acquire_mut() {
+			Some(borrow) => {
+				self.record_origin();
+				borrow
+			},
+			None => self.conflict(ViolationKind::AlreadyBorrowed, name)
+		}
+	}
+
+	#[inline]
+	#[track_caller]
+	fn borrow_upgradable(&self, name: Option<&'static str>) -> BorrowRef<'_> {
+		self.borrow(name)
+	}
+
+	#[inline]
+	#[track_caller]
+	fn upgrade<'a>(&'a self, borrow: BorrowRef<'a>, name: Option<&'static str>) -> BorrowRefMut<'a> {
+		// The upgradable borrow is turned into the mutable borrow in place, so no other borrow can be acquired in between
+		match self.count.compare_exchange(1, -1, Ordering::Acquire, Ordering::Relaxed) {
+			Ok(_) => {
+				core::mem::forget(borrow);
+				BorrowRefMut(self)
+			},
+			Err(_) => self.conflict(ViolationKind::AlreadyBorrowed, name)
+		}
+	}
+
+	#[inline]
+	#[track_caller]
+	fn try_borrow(&self) -> Option<BorrowRef<'_>> {
+		self.check_thread(None);
+		let borrow = self.acquire()?;
+		self.record_origin();
+		Some(borrow)
+	}
+
+	#[inline]
+	#[track_caller]
+	fn try_borrow_mut(&self) -> Option<BorrowRefMut<'_>> {
+		self.check_thread(None);
+		let borrow = self.acquire_mut()?;
+		self.record_origin();
+		Some(borrow)
+	}
+
+	#[inline]
+	fn split<'a>(borrow: BorrowRefMut<'a>) -> (BorrowRefMut<'a>, BorrowRefMut<'a>)
+	where
+		Self: 'a
+	{
+		borrow.0.count.fetch_sub(1, Ordering::Relaxed);
+		let other = BorrowRefMut(borrow.0);
+		(borrow, other)
+	}
+
+	#[inline]
+	fn immutable_borrow_count(&self) -> usize {
+		self.count.load(Ordering::Relaxed).max(0) as usize
+	}
+
+	#[inline]
+	fn has_mutable_borrow(&self) -> bool {
+		self.count.load(Ordering::Relaxed) < 0
+	}
+
+	#[inline]
+	#[cfg(feature = "test-util")]
+	fn reset_thread(&self) {
+		self.owner.store(0, Ordering::Relaxed);
+	}
+}
+
+/// Spins on conflicting borrows until they are released. Used with the `spin` feature.
+///
+/// Singletons can be shared between threads with this, so it never checks thread ids.
+#[cfg(any(test, all(feature = "spin", not(feature = "sync"))))]
+pub(crate) struct SpinLock(BorrowFlag);
+#[cfg(any(test, all(feature = "spin", not(feature = "sync"))))]
+impl BorrowLock for SpinLock {
+	type Borrow<'a> = BorrowRef<'a>;
+	type BorrowMut<'a> = BorrowRefMut<'a>;
+	// Two upgradable borrows would each spin forever waiting for the other to be released, so they are exclusive
+	type UpgradableBorrow<'a> = BorrowRefMut<'a>;
+
+	const INIT: Self = SpinLock(BorrowFlag::INIT);
+
+	#[inline]
+	fn borrow(&self, _: Option<&'static str>) -> BorrowRef<'_> {
+		loop {
+			if let Some(borrow) = self.0.acquire() {
+				return borrow;
+			}
+			core::hint::spin_loop();
+		}
+	}
+
+	#[inline]
+	fn borrow_mut(&self, _: Option<&'static str>) -> BorrowRefMut<'_> {
+		loop {
+			if let Some(borrow) = self.0.acquire_mut() {
+				return borrow;
+			}
+			core::hint::spin_loop();
+		}
+	}
+
+	#[inline]
+	fn borrow_upgradable(&self, name: Option<&'static str>) -> BorrowRefMut<'_> {
+		self.borrow_mut(name)
+	}
+
+	#[inline]
+	fn upgrade<'a>(&'a self, borrow: BorrowRefMut<'a>, _: Option<&'static str>) -> BorrowRefMut<'a> {
+		borrow
+	}
+
+	#[inline]
+	fn try_borrow(&self) -> Option<BorrowRef<'_>> {
+		self.0.acquire()
+	}
+
+	#[inline]
+	fn try_borrow_mut(&self) -> Option<BorrowRefMut<'_>> {
+		self.0.acquire_mut()
+	}
+
+	#[inline]
+	fn split<'a>(borrow: BorrowRefMut<'a>) -> (BorrowRefMut<'a>, BorrowRefMut<'a>)
+	where
+		Self: 'a
+	{
+		BorrowFlag::split(borrow)
+	}
+
+	#[inline]
+	fn immutable_borrow_count(&self) -> usize {
+		self.0.immutable_borrow_count()
+	}
+
+	#[inline]
+	fn has_mutable_borrow(&self) -> bool {
+		self.0.has_mutable_borrow()
+	}
+}
+
+// Split `SinglytonRefMut`s share the write lock, which is released when the last of them is dropped.
+#[cfg(any(test, feature = "sync"))]
+pub(crate) enum WriteGuard<'a> {
+	Unique(std::sync::RwLockWriteGuard<'a, ()>),
+	Shared(std::rc::Rc<std::sync::RwLockWriteGuard<'a, ()>>)
+}
+
+/// Blocks on conflicting borrows until they are released. Used with the `sync` feature.
+#[cfg(any(test, feature = "sync"))]
+impl BorrowLock for std::sync::RwLock<()> {
+	type Borrow<'a> = std::sync::RwLockReadGuard<'a, ()>;
+	type BorrowMut<'a> = WriteGuard<'a>;
+	// `RwLock` cannot upgrade a read guard, so upgradable borrows take the write lock up front
+	type UpgradableBorrow<'a> = WriteGuard<'a>;
+
+	const INIT: Self = std::sync::RwLock::new(());
+
+	#[inline]
+	fn borrow(&self, _: Option<&'static str>) -> Self::Borrow<'_> {
+		self.read().unwrap_or_else(std::sync::PoisonError::into_inner)
+	}
+
+	#[inline]
+	fn borrow_mut(&self, _: Option<&'static str>) -> WriteGuard<'_> {
+		WriteGuard::Unique(self.write().unwrap_or_else(std::sync::PoisonError::into_inner))
+	}
+
+	#[inline]
+	fn borrow_upgradable(&self, name: Option<&'static str>) -> WriteGuard<'_> {
+		self.borrow_mut(name)
+	}
+
+	#[inline]
+	fn upgrade<'a>(&'a self, borrow: WriteGuard<'a>, _: Option<&'static str>) -> WriteGuard<'a> {
+		borrow
+	}
+
+	#[inline]
+	fn try_borrow(&self) -> Option<Self::Borrow<'_>> {
+		match self.try_read() {
+			Ok(guard) => Some(guard),
+			Err(std::sync::TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+			Err(std::sync::TryLockError::WouldBlock) => None
+		}
+	}
+
+	#[inline]
+	fn try_borrow_mut(&self) -> Option<WriteGuard<'_>> {
+		match self.try_write() {
+			Ok(guard) => Some(WriteGuard::Unique(guard)),
+			Err(std::sync::TryLockError::Poisoned(poisoned)) => Some(WriteGuard::Unique(poisoned.into_inner())),
+			Err(std::sync::TryLockError::WouldBlock) => None
+		}
+	}
+
+	#[inline]
+	fn split<'a>(borrow: WriteGuard<'a>) -> (WriteGuard<'a>, WriteGuard<'a>)
+	where
+		Self: 'a
+	{
+		let shared = match borrow {
+			WriteGuard::Unique(guard) => std::rc::Rc::new(guard),
+			WriteGuard::Shared(shared) => shared
+		};
+		(WriteGuard::Shared(shared.clone()), WriteGuard::Shared(shared))
+	}
+}
\ No newline at end of file
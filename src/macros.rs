@@ -0,0 +1,73 @@
+#[macro_export]
+/// Declares a `static` [`Singleton`](crate::Singleton) named after its module-qualified path, which is included in the panic messages of its debug checks.
+///
+/// ```
+/// singlyton::named_singleton!(pub COUNTER: u32 = 0);
+///
+/// *COUNTER.get_mut() += 1;
+/// debug_assert_eq!(*COUNTER.get(), 1);
+/// ```
+///
+/// The name is only stored in debug builds.
+macro_rules! named_singleton {
+	($(#[$attr:meta])* $vis:vis $name:ident: $ty:ty = $val:expr $(;)?) => {
+		$(#[$attr])*
+		$vis static $name: $crate::Singleton<$ty> = $crate::Singleton::new_named($val, concat!(module_path!(), "::", stringify!($name)));
+	};
+}
+
+#[macro_export]
+/// Implements [`GlobalState`](crate::GlobalState) for a type, backed by a hidden `static` [`SingletonUninit`](crate::SingletonUninit).
+///
+/// ```
+/// use singlyton::GlobalState;
+///
+/// struct Config {
+///     verbose: bool
+/// }
+/// singlyton::global_state!(Config);
+///
+/// Config::initialize(Config { verbose: true });
+/// debug_assert!(Config::instance().get().verbose);
+/// Config::shutdown();
+/// ```
+macro_rules! global_state {
+	($ty:ty $(;)?) => {
+		impl $crate::GlobalState for $ty {
+			#[inline]
+			fn instance() -> &'static $crate::SingletonUninit<$ty> {
+				static INSTANCE: $crate::SingletonUninit<$ty> = $crate::SingletonUninit::uninit();
+				&INSTANCE
+			}
+		}
+	};
+}
+
+#[macro_export]
+#[cfg(feature = "std")]
+/// Declares a `static` [`ThreadLocalSingleton`](crate::ThreadLocalSingleton), which has a separate instance for each thread.
+///
+/// Each thread's instance is initialized with `$val` on its first access.
+///
+/// ```
+/// singlyton::thread_local_singleton!(static SCRATCH: Vec<u32> = Vec::new());
+///
+/// SCRATCH.with_mut(|scratch| scratch.push(1));
+///
+/// std::thread::spawn(|| {
+///     debug_assert!(SCRATCH.with(Vec::is_empty));
+/// }).join().unwrap();
+///
+/// debug_assert_eq!(SCRATCH.get_cloned(), [1]);
+/// ```
+macro_rules! thread_local_singleton {
+	($(#[$attr:meta])* $vis:vis static $name:ident: $ty:ty = $val:expr $(;)?) => {
+		$(#[$attr])*
+		$vis static $name: $crate::ThreadLocalSingleton<$ty> = {
+			::std::thread_local! {
+				static INSTANCE: $crate::Singleton<$ty> = $crate::Singleton::new_named($val, concat!(module_path!(), "::", stringify!($name)));
+			}
+			$crate::ThreadLocalSingleton::new(&INSTANCE)
+		};
+	};
+}
\ No newline at end of file
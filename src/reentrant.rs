@@ -0,0 +1,69 @@
+use core::{cell::UnsafeCell, sync::atomic::{AtomicUsize, Ordering}};
+
+struct DepthGuard<'a>(&'a AtomicUsize);
+impl Drop for DepthGuard<'_> {
+	#[inline]
+	fn drop(&mut self) {
+		self.0.fetch_sub(1, Ordering::Relaxed);
+	}
+}
+
+/// A **thread-unsafe** global singleton which permits nested (reentrant) mutable access on the same thread.
+///
+/// A `Singleton` panics in debug builds if `get_mut` is called while a mutable reference is already held, which gets in the way of recursive algorithms that mutate a global at every level. This singleton instead hands out **mutable pointers** with `with_mut_ptr`, which can be nested arbitrarily deep (or up to a limit, see `new_reentrant_limited`).
+///
+/// # Soundness
+///
+/// Nested calls hand out aliasing mutable pointers to the same value, which is why this singleton has no reference-based API. Creating a reference from one of these pointers is only sound if no reference created from an outer pointer is used afterwards, so keep each reference short-lived and never hold one across a nested call.
+pub struct SingletonReentrant<T> {
+	value: UnsafeCell<T>,
+	depth: AtomicUsize,
+	max_depth: usize
+}
+#[cfg(not(any(feature = "sync", feature = "spin")))]
+unsafe impl<T> Sync for SingletonReentrant<T> {}
+#[cfg(any(feature = "sync", feature = "spin"))]
+unsafe impl<T: Send + Sync> Sync for SingletonReentrant<T> {}
+
+impl<T> SingletonReentrant<T> {
+	#[inline]
+	pub const fn new_reentrant(val: T) -> Self {
+		Self::new_reentrant_limited(val, usize::MAX)
+	}
+
+	#[inline]
+	/// Creates a reentrant singleton which panics if `with_mut_ptr` is nested more than `max_depth` calls deep.
+	///
+	/// This is useful for catching runaway recursion.
+	pub const fn new_reentrant_limited(val: T, max_depth: usize) -> Self {
+		Self {
+			value: UnsafeCell::new(val),
+			depth: AtomicUsize::new(0),
+			max_depth
+		}
+	}
+
+	#[inline]
+	/// Calls `f` with a **mutable pointer** to the singleton. This can be called again from within `f`.
+	///
+	/// See the [soundness](SingletonReentrant#soundness) notes for how the pointer can be used.
+	///
+	/// This will panic if the call is nested deeper than the singleton's maximum depth.
+	pub fn with_mut_ptr<R, F>(&'static self, f: F) -> R
+	where
+		F: FnOnce(*mut T) -> R
+	{
+		let depth = self.depth.fetch_add(1, Ordering::Relaxed) + 1;
+		let _guard = DepthGuard(&self.depth);
+		if depth > self.max_depth {
+			panic!("singleton exceeded its maximum reentrancy depth of {}", self.max_depth);
+		}
+		f(self.value.get())
+	}
+
+	#[inline]
+	/// Returns how many calls to `with_mut_ptr` are currently in progress.
+	pub fn depth(&'static self) -> usize {
+		self.depth.load(Ordering::Relaxed)
+	}
+}
\ No newline at end of file
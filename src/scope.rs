@@ -0,0 +1,33 @@
+/// A scope which limits how long singleton borrows may be held, enforced at compile time.
+///
+/// Borrows acquired with `Singleton::get_scoped` and `Singleton::get_mut_scoped` cannot outlive the scope they were acquired in, so holding a guard for too long is a compile error rather than a runtime panic.
+///
+/// ```
+/// use singlyton::{BorrowScope, Singleton};
+///
+/// static SINGLETON: Singleton<u32> = Singleton::new(0);
+///
+/// let scope = BorrowScope::new();
+/// *SINGLETON.get_mut_scoped(&scope) += 1;
+/// debug_assert_eq!(*SINGLETON.get_scoped(&scope), 1);
+/// ```
+///
+/// ```compile_fail
+/// use singlyton::{BorrowScope, Singleton};
+///
+/// static SINGLETON: Singleton<u32> = Singleton::new(0);
+///
+/// let guard = {
+///     let scope = BorrowScope::new();
+///     SINGLETON.get_mut_scoped(&scope)
+/// };
+/// ```
+#[derive(Debug, Default)]
+pub struct BorrowScope(());
+
+impl BorrowScope {
+	#[inline]
+	pub const fn new() -> Self {
+		Self(())
+	}
+}
\ No newline at end of file
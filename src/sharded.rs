@@ -0,0 +1,65 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+use crate::{SingletonArray, SinglytonRef, SinglytonRefMut};
+
+static NEXT_SHARD: AtomicUsize = AtomicUsize::new(0);
+
+std::thread_local! {
+	// Threads are spread over the shards in the order they first access a sharded singleton
+	static SHARD: usize = NEXT_SHARD.fetch_add(1, Ordering::Relaxed);
+}
+
+/// A global which is striped across `SHARDS` independently locked copies, to reduce contention between threads.
+///
+/// Each thread always accesses the same shard, so threads only block each other if they share one. This is useful for write-heavy globals that can be combined later, such as counters and accumulators, which would otherwise serialize every thread on a single lock.
+///
+/// Use `reduce` to combine the shards into a single value.
+///
+/// # Panics
+///
+/// Accessing a singleton with no shards will always panic.
+pub struct ShardedSingleton<T, const SHARDS: usize>(SingletonArray<T, SHARDS>);
+
+impl<T, const SHARDS: usize> ShardedSingleton<T, SHARDS> {
+	#[inline]
+	pub const fn new(shards: [T; SHARDS]) -> Self {
+		Self(SingletonArray::new(shards))
+	}
+
+	#[inline]
+	fn shard(&self) -> usize {
+		SHARD.with(|shard| *shard) % SHARDS
+	}
+
+	#[inline]
+	#[track_caller]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires an **immutable reference** to the current thread's shard, blocking until any mutable reference to it is released.
+	pub fn get(&'static self) -> SinglytonRef<'static, T> {
+		self.0.get(self.shard())
+	}
+
+	#[inline]
+	#[track_caller]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **mutable reference** to the current thread's shard, blocking until any other reference to it is released.
+	pub fn get_mut(&'static self) -> SinglytonRefMut<'static, T> {
+		self.0.get_mut(self.shard())
+	}
+
+	#[inline]
+	/// Combines every shard into a single value, by folding `f` over them starting from `init`.
+	///
+	/// Each shard is only borrowed while `f` is called on it, so other threads can keep writing to the other shards in the meantime.
+	pub fn reduce<R, F>(&'static self, init: R, mut f: F) -> R
+	where
+		F: FnMut(R, &T) -> R
+	{
+		(0..SHARDS).fold(init, |acc, shard| f(acc, &self.0.get(shard)))
+	}
+
+	#[inline]
+	/// The number of shards.
+	pub const fn shards(&self) -> usize {
+		SHARDS
+	}
+}
\ No newline at end of file
@@ -0,0 +1,91 @@
+use crate::{map_ref, map_ref_mut, SingletonUninit, SinglytonRef, SinglytonRefMut};
+use alloc::boxed::Box;
+
+/// A **thread-unsafe** global singleton containing a slice whose length is only known at runtime.
+///
+/// The slice is stored on the heap and must be initialized with `init` before use.
+///
+/// Using this across threads is undefined behaviour.
+///
+/// Requires the `alloc` feature.
+///
+/// # Panics
+///
+/// In debug builds, usage of this abstraction is checked for safety at runtime.
+///
+/// * Using this struct across threads will panic, once a thread id provider has been set with `set_thread_id_provider`.
+/// * Mixing mutabilty of borrows will panic.
+/// * Using this struct before initializing it will panic.
+/// * Initializing the slice more than once will panic.
+pub struct SingletonSlice<T>(SingletonUninit<Box<[T]>>);
+
+impl<T> SingletonSlice<T> {
+	#[inline]
+	pub const fn uninit() -> Self {
+		Self(SingletonUninit::uninit())
+	}
+
+	#[inline]
+	/// Initializes the slice in the singleton.
+	///
+	/// In debug builds, this will panic if the slice is **already initialized**, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	pub fn init<S: Into<Box<[T]>>>(&'static self, slice: S) {
+		self.0.init(slice.into());
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires an **immutable reference** to the slice.
+	///
+	/// In debug builds, this will panic if the slice is not initialized, the singleton is mutably accessed from a different thread, or a mutable reference is currently held.
+	pub fn get(&'static self) -> SinglytonRef<'static, [T]> {
+		map_ref(self.0.get(), |slice| &**slice)
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **mutable reference** to the slice.
+	///
+	/// In debug builds, this will panic if the slice is not initialized, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	pub fn get_mut(&'static self) -> SinglytonRefMut<'static, [T]> {
+		map_ref_mut(self.0.get_mut(), |slice| &mut **slice)
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires an **immutable reference** to the element at `index`.
+	///
+	/// Panics if `index` is out of bounds.
+	///
+	/// In debug builds, this will panic if the slice is not initialized, the singleton is mutably accessed from a different thread, or a mutable reference is currently held.
+	pub fn index(&'static self, index: usize) -> SinglytonRef<'static, T> {
+		map_ref(self.get(), |slice| &slice[index])
+	}
+
+	#[inline]
+	#[must_use = "acquiring a singleton borrow has no effect if dropped immediately"]
+	/// Acquires a **mutable reference** to the element at `index`.
+	///
+	/// Panics if `index` is out of bounds.
+	///
+	/// In debug builds, this will panic if the slice is not initialized, the singleton is mutably accessed from a different thread, or an existing mutable or immutable reference is currently held.
+	pub fn index_mut(&'static self, index: usize) -> SinglytonRefMut<'static, T> {
+		map_ref_mut(self.get_mut(), |slice| &mut slice[index])
+	}
+
+	#[inline]
+	/// Returns the length of the slice.
+	///
+	/// In debug builds, this will panic if the slice is not initialized, the singleton is mutably accessed from a different thread, or a mutable reference is currently held.
+	pub fn len(&'static self) -> usize {
+		self.0.get().len()
+	}
+
+	#[inline]
+	/// Tests if the slice is empty.
+	///
+	/// In debug builds, this will panic if the slice is not initialized, the singleton is mutably accessed from a different thread, or a mutable reference is currently held.
+	pub fn is_empty(&'static self) -> bool {
+		self.0.get().is_empty()
+	}
+}
\ No newline at end of file
@@ -0,0 +1,83 @@
+use alloc::collections::VecDeque;
+use crate::Singleton;
+
+/// Identifies a snapshot captured by a `SnapshotHistory`.
+///
+/// Ids increase with each capture, so later snapshots compare greater than earlier ones.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SnapshotId(u64);
+
+/// A ring buffer of the most recent snapshots of a singleton, which it can be rewound to.
+///
+/// This is intended for deterministic replay and rollback, such as in simulations and netcode, where global state is captured at frame boundaries and restored when the past needs to be replayed.
+///
+/// Once the history is full, capturing a snapshot discards the oldest one.
+pub struct SnapshotHistory<T> {
+	snapshots: VecDeque<(SnapshotId, T)>,
+	capacity: usize,
+	next: u64
+}
+impl<T: Clone> SnapshotHistory<T> {
+	#[inline]
+	/// Creates an empty history which keeps up to `capacity` snapshots.
+	///
+	/// Panics if `capacity` is zero.
+	pub fn new(capacity: usize) -> Self {
+		assert!(capacity > 0, "snapshot history capacity must not be zero");
+		Self {
+			snapshots: VecDeque::with_capacity(capacity),
+			capacity,
+			next: 0
+		}
+	}
+
+	/// Captures a snapshot of `singleton`, discarding the oldest snapshot if the history is full.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or if a mutable reference is currently held.
+	pub fn capture(&mut self, singleton: &'static Singleton<T>) -> SnapshotId {
+		if self.snapshots.len() == self.capacity {
+			self.snapshots.pop_front();
+		}
+
+		let id = SnapshotId(self.next);
+		self.next += 1;
+		self.snapshots.push_back((id, singleton.snapshot()));
+		id
+	}
+
+	/// Restores `singleton` to the snapshot `id`, discarding every snapshot captured after it.
+	///
+	/// The snapshot itself is kept, so the singleton can be rewound to it again. Returns `false` and leaves the singleton unchanged if the snapshot has already been discarded.
+	///
+	/// In debug builds, this will panic if the singleton is mutably accessed from a different thread or an existing mutable or immutable reference is currently held.
+	pub fn rewind(&mut self, singleton: &'static Singleton<T>, id: SnapshotId) -> bool {
+		let Ok(index) = self.snapshots.binary_search_by_key(&id, |(id, _)| *id) else { return false };
+		self.snapshots.truncate(index + 1);
+		singleton.restore(self.snapshots[index].1.clone());
+		true
+	}
+
+	#[inline]
+	/// Returns the snapshot `id`, or `None` if it has been discarded.
+	pub fn get(&self, id: SnapshotId) -> Option<&T> {
+		self.snapshots.binary_search_by_key(&id, |(id, _)| *id).ok().map(|index| &self.snapshots[index].1)
+	}
+
+	#[inline]
+	/// Returns the id of the most recent snapshot, if any.
+	pub fn latest(&self) -> Option<SnapshotId> {
+		self.snapshots.back().map(|(id, _)| *id)
+	}
+
+	#[inline]
+	/// The number of snapshots in the history.
+	pub fn len(&self) -> usize {
+		self.snapshots.len()
+	}
+
+	#[inline]
+	/// Tests if the history has no snapshots.
+	pub fn is_empty(&self) -> bool {
+		self.snapshots.is_empty()
+	}
+}
\ No newline at end of file
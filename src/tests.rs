@@ -28,15 +28,15 @@ fn test_singleton_uninit() {
 
 #[test]
 #[should_panic]
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, not(feature = "abort-on-violation")))]
 fn test_singleton_uninit_panic() {
 	static SINGLETON: SingletonUninit<String> = SingletonUninit::uninit();
-	SINGLETON.get();
+	let _ = SINGLETON.get();
 }
 
 #[test]
 #[should_panic]
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
 fn test_refcell() {
 	static SINGLETON: Singleton<&'static str> = Singleton::new("Hello");
 	let _my_ref = SINGLETON.get();
@@ -45,36 +45,2126 @@ fn test_refcell() {
 
 #[test]
 #[should_panic]
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
 fn test_thread_safety() {
 	static SINGLETON: Singleton<&'static str> = Singleton::new("Hello");
 	let held_ref = SINGLETON.get();
 
-	std::thread::spawn(|| SINGLETON.get_mut()).join().unwrap();
+	std::thread::spawn(|| drop(SINGLETON.get_mut())).join().unwrap();
 
 	drop(held_ref);
 }
 
 #[test]
 #[should_panic]
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
 fn test_thread_safety_2() {
 	static SINGLETON: Singleton<&'static str> = Singleton::new("Hello");
 	let held_ref = SINGLETON.get_mut();
 
-	std::thread::spawn(|| SINGLETON.get()).join().unwrap();
+	std::thread::spawn(|| drop(SINGLETON.get())).join().unwrap();
 
 	drop(held_ref);
 }
 
 #[test]
 #[should_panic]
-#[cfg(debug_assertions)]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
 fn test_thread_safety_3() {
 	static SINGLETON: Singleton<&'static str> = Singleton::new("Hello");
 	let held_ref = SINGLETON.get_mut();
 
-	std::thread::spawn(|| SINGLETON.get_mut()).join().unwrap();
+	std::thread::spawn(|| drop(SINGLETON.get_mut())).join().unwrap();
 
 	drop(held_ref);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_drop_group() {
+	static DROPPED: Singleton<Vec<&'static str>> = Singleton::new(Vec::new());
+
+	struct Resource(&'static str);
+	impl Drop for Resource {
+		fn drop(&mut self) {
+			DROPPED.get_mut().push(self.0);
+		}
+	}
+
+	static LOGGER: SingletonUninit<Resource> = SingletonUninit::uninit();
+	static NETWORK: SingletonUninit<Resource> = SingletonUninit::uninit();
+	static CACHE: SingletonUninit<Resource> = SingletonUninit::uninit();
+	static GROUP: DropGroup = DropGroup::new();
+
+	LOGGER.init(Resource("logger"));
+	NETWORK.init(Resource("network"));
+	CACHE.init(Resource("cache"));
+
+	GROUP.register(&LOGGER, 0);
+	GROUP.register(&NETWORK, 10);
+	GROUP.register(&CACHE, 5);
+
+	debug_assert!(DROPPED.get().is_empty());
+	GROUP.shutdown();
+	debug_assert_eq!(*DROPPED.get(), ["network", "cache", "logger"]);
+
+	LOGGER.init(Resource("logger"));
+	debug_assert_eq!(LOGGER.get().0, "logger");
+}
+
+#[test]
+#[cfg(all(feature = "alloc", not(feature = "abort-on-violation")))]
+fn test_drop_group_shutdown_uninitialized() {
+	static DROPPED: Singleton<Vec<&'static str>> = Singleton::new(Vec::new());
+
+	struct Resource(&'static str);
+	impl Drop for Resource {
+		fn drop(&mut self) {
+			DROPPED.get_mut().push(self.0);
+		}
+	}
+
+	static SERVER: SingletonUninit<Resource> = SingletonUninit::uninit();
+	static MISSING: SingletonUninit<Resource> = SingletonUninit::uninit();
+	static LOGGER: SingletonUninit<Resource> = SingletonUninit::uninit();
+	static GROUP: DropGroup = DropGroup::new();
+
+	GROUP.init(&SERVER, Resource("server"), 10);
+	GROUP.register(&MISSING, 5);
+	GROUP.init(&LOGGER, Resource("logger"), 0);
+
+	assert!(std::panic::catch_unwind(|| GROUP.shutdown()).is_err());
+	debug_assert_eq!(*DROPPED.get(), ["server"]);
+
+	// The singletons after the uninitialized one are still registered
+	GROUP.shutdown();
+	debug_assert_eq!(*DROPPED.get(), ["server", "logger"]);
+}
+
+#[test]
+fn test_singleton_option_get_or_insert_default() {
+	static SINGLETON: SingletonOption<String> = SingletonOption::new();
+	debug_assert!(SINGLETON.is_none());
+
+	SINGLETON.get_or_insert_default().push('a');
+	debug_assert!(SINGLETON.is_some());
+
+	SINGLETON.get_or_insert_default().push('b');
+	debug_assert_eq!(SINGLETON.get().as_str(), "ab");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_singleton_atomic_box() {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+	struct Pair(usize, usize);
+	impl Drop for Pair {
+		fn drop(&mut self) {
+			DROPS.fetch_add(1, Ordering::SeqCst);
+		}
+	}
+
+	static SINGLETON: SingletonAtomicBox<Pair> = SingletonAtomicBox::new();
+	debug_assert!(SINGLETON.load().is_none());
+
+	let loaders = (0..4).map(|_| std::thread::spawn(|| {
+		let mut last = 0;
+		loop {
+			if let Some(pair) = SINGLETON.load() {
+				assert_eq!(pair.0, pair.1);
+				assert!(pair.0 >= last);
+				last = pair.0;
+				if last == 1000 {
+					break;
+				}
+			}
+		}
+	})).collect::<Vec<_>>();
+
+	let storer = std::thread::spawn(|| {
+		for i in 0..=1000 {
+			SINGLETON.store(Box::new(Pair(i, i)));
+		}
+	});
+
+	storer.join().unwrap();
+	for loader in loaders {
+		loader.join().unwrap();
+	}
+
+	let latest = SINGLETON.load().unwrap();
+	debug_assert_eq!((latest.0, latest.1), (1000, 1000));
+
+	SINGLETON.store(Box::new(Pair(1001, 1001)));
+	debug_assert_eq!((latest.0, latest.1), (1000, 1000));
+	debug_assert_eq!(SINGLETON.load().unwrap().0, 1001);
+	debug_assert_eq!(DROPS.load(Ordering::SeqCst), 0);
+
+	// Every value but the latest is dropped once no readers can hold references to them
+	unsafe { SINGLETON.reclaim() };
+	debug_assert_eq!(DROPS.load(Ordering::SeqCst), 1001);
+	debug_assert_eq!(SINGLETON.load().unwrap().0, 1001);
+
+	unsafe { SINGLETON.reclaim() };
+	debug_assert_eq!(DROPS.load(Ordering::SeqCst), 1001);
+}
+
+#[test]
+fn test_singleton_map_cloned() {
+	struct Config {
+		name: String,
+		#[allow(dead_code)]
+		port: u16
+	}
+
+	static SINGLETON: Singleton<Config> = Singleton::new(Config { name: String::new(), port: 80 });
+	SINGLETON.get_mut().name.push_str("localhost");
+
+	let name = SINGLETON.map_cloned(|config| &config.name);
+	debug_assert_eq!(name, "localhost");
+
+	SINGLETON.get_mut().name.clear();
+	debug_assert_eq!(name, "localhost");
+}
+
+#[test]
+fn test_singleton_upgradable_read() {
+	static SINGLETON: Singleton<Vec<u32>> = Singleton::new(Vec::new());
+
+	let read = SINGLETON.upgradable_read();
+	debug_assert!(read.is_empty());
+
+	let mut write = SinglytonUpgradableRef::upgrade(read);
+	write.push(1);
+	drop(write);
+
+	debug_assert_eq!(*SINGLETON.get(), [1]);
+}
+
+#[test]
+#[should_panic]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+fn test_singleton_upgradable_read_panic() {
+	static SINGLETON: Singleton<Vec<u32>> = Singleton::new(Vec::new());
+
+	let read = SINGLETON.upgradable_read();
+	let _other_read = SINGLETON.get();
+	let _write = SinglytonUpgradableRef::upgrade(read);
+}
+
+#[test]
+#[cfg(feature = "observers")]
+fn test_singleton_upgradable_read_observed() {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	static NOTIFIED: AtomicUsize = AtomicUsize::new(0);
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+
+	SINGLETON.add_observer(|_| {
+		NOTIFIED.fetch_add(1, Ordering::SeqCst);
+	});
+
+	let read = SINGLETON.upgradable_read();
+	drop(read);
+	assert_eq!(NOTIFIED.load(Ordering::SeqCst), 0);
+
+	let read = SINGLETON.upgradable_read();
+	*SinglytonUpgradableRef::upgrade(read) += 1;
+	assert_eq!(NOTIFIED.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+#[cfg(all(debug_assertions, feature = "structured-panic", not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+fn test_structured_panic() {
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+	static SINGLETON_UNINIT: SingletonUninit<u32> = SingletonUninit::uninit();
+
+	let payload = std::panic::catch_unwind(|| {
+		let _ref = SINGLETON.get();
+		let _mut_ref = SINGLETON.get_mut();
+	}).unwrap_err();
+	let violation = payload.downcast_ref::<BorrowViolation>().unwrap();
+	debug_assert_eq!(violation.kind, ViolationKind::AlreadyBorrowed);
+
+	let payload = std::panic::catch_unwind(|| {
+		let _ref = SINGLETON_UNINIT.get();
+	}).unwrap_err();
+	let violation = payload.downcast_ref::<BorrowViolation>().unwrap();
+	debug_assert_eq!(violation.kind, ViolationKind::Uninitialized);
+
+	static DEFERRED: DeferredSingleton<u32> = DeferredSingleton::new();
+
+	let payload = std::panic::catch_unwind(|| {
+		let _ref = DEFERRED.get();
+	}).unwrap_err();
+	let violation = payload.downcast_ref::<BorrowViolation>().unwrap();
+	debug_assert_eq!(violation.kind, ViolationKind::Uninitialized);
+
+	DEFERRED.set_once(1);
+	let payload = std::panic::catch_unwind(|| DEFERRED.set_once(2)).unwrap_err();
+	let violation = payload.downcast_ref::<BorrowViolation>().unwrap();
+	debug_assert_eq!(violation.kind, ViolationKind::AlreadyInitialized);
+
+	static RECURSIVE: SingletonLazy<u32> = SingletonLazy::new(|| *RECURSIVE.get() + 1);
+
+	let payload = std::panic::catch_unwind(|| *RECURSIVE.get()).unwrap_err();
+	let violation = payload.downcast_ref::<BorrowViolation>().unwrap();
+	debug_assert_eq!(violation.kind, ViolationKind::RecursiveInitialization);
+
+	let payload = std::panic::catch_unwind(|| *RECURSIVE.get()).unwrap_err();
+	let violation = payload.downcast_ref::<BorrowViolation>().unwrap();
+	debug_assert_eq!(violation.kind, ViolationKind::Poisoned);
+}
+
+#[test]
+fn test_singleton_uninit_get_or_init_reporting() {
+	static SINGLETON: SingletonUninit<String> = SingletonUninit::uninit();
+
+	let (value, initialized) = SINGLETON.get_or_init_reporting(|| "Hello".to_string());
+	debug_assert!(initialized);
+	debug_assert_eq!(value.as_str(), "Hello");
+	drop(value);
+
+	let (value, initialized) = SINGLETON.get_or_init_reporting(|| unreachable!());
+	debug_assert!(!initialized);
+	debug_assert_eq!(value.as_str(), "Hello");
+}
+
+#[test]
+fn test_guard_as_ref() {
+	fn len(s: impl AsRef<str>) -> usize {
+		s.as_ref().len()
+	}
+
+	fn sum(bytes: impl AsRef<[u8]>) -> u32 {
+		bytes.as_ref().iter().map(|byte| *byte as u32).sum()
+	}
+
+	static STRING: Singleton<String> = Singleton::new(String::new());
+	static BYTES: Singleton<Vec<u8>> = Singleton::new(Vec::new());
+
+	STRING.get_mut().push_str("Hello");
+	BYTES.get_mut().extend_from_slice(&[1, 2, 3]);
+
+	debug_assert_eq!(len(STRING.get()), 5);
+	debug_assert_eq!(sum(BYTES.get()), 6);
+	debug_assert_eq!(sum(BYTES.get_mut()), 6);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_singleton_slice() {
+	static SINGLETON: SingletonSlice<u32> = SingletonSlice::uninit();
+
+	let len = std::hint::black_box(16);
+	SINGLETON.init(vec![0; len]);
+	debug_assert_eq!(SINGLETON.len(), 16);
+	debug_assert!(!SINGLETON.is_empty());
+
+	*SINGLETON.index_mut(3) = 3;
+	SINGLETON.get_mut()[15] = 15;
+
+	debug_assert_eq!(*SINGLETON.index(3), 3);
+	debug_assert_eq!(SINGLETON.get()[15], 15);
+	debug_assert_eq!(SINGLETON.get().iter().sum::<u32>(), 18);
+}
+
+#[test]
+fn test_singleton_uninit_try_init() {
+	static SINGLETON: SingletonUninit<String> = SingletonUninit::uninit();
+
+	debug_assert_eq!(SINGLETON.try_init("Hello".to_string()), Ok(()));
+	debug_assert_eq!(SINGLETON.try_init("Test".to_string()), Err("Test".to_string()));
+	debug_assert_eq!(SINGLETON.get().as_str(), "Hello");
+}
+
+#[test]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+fn test_singleton_uninit_try_init_while_borrowed() {
+	static SINGLETON: SingletonUninit<u32> = SingletonUninit::new(1);
+
+	// Held references are reported just like by `init`, even though the memory is already initialized
+	let guard = SINGLETON.get();
+	assert!(std::panic::catch_unwind(|| SINGLETON.try_init(2)).is_err());
+	drop(guard);
+
+	assert!(SINGLETON.try_init(2).is_err());
+}
+
+#[test]
+fn test_singleton_option_get_mut_or() {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+	struct Tracked(Vec<u32>);
+	impl Drop for Tracked {
+		fn drop(&mut self) {
+			DROPS.fetch_add(1, Ordering::SeqCst);
+		}
+	}
+
+	static SINGLETON: SingletonOption<Tracked> = SingletonOption::new();
+
+	SINGLETON.get_mut_or(Tracked(vec![1])).0.push(2);
+	debug_assert_eq!(SINGLETON.get().0, [1, 2]);
+	debug_assert_eq!(DROPS.load(Ordering::SeqCst), 0);
+
+	SINGLETON.get_mut_or(Tracked(vec![])).0.push(3);
+	debug_assert_eq!(SINGLETON.get().0, [1, 2, 3]);
+	debug_assert_eq!(DROPS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+#[cfg(any(feature = "sync", feature = "spin"))]
+fn test_sync() {
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+
+	let threads = (0..8).map(|_| std::thread::spawn(|| {
+		for _ in 0..1000 {
+			*SINGLETON.get_mut() += 1;
+		}
+	})).collect::<Vec<_>>();
+
+	for thread in threads {
+		thread.join().unwrap();
+	}
+
+	debug_assert_eq!(*SINGLETON.get(), 8000);
+}
+
+#[test]
+fn test_singleton_option_set_unset() {
+	static SINGLETON: SingletonOption<String> = SingletonOption::new();
+	debug_assert!(SINGLETON.is_none());
+
+	SINGLETON.set("Hello".to_string());
+	debug_assert!(SINGLETON.is_some());
+	debug_assert_eq!(SINGLETON.get().as_str(), "Hello");
+
+	SINGLETON.set("Test".to_string());
+	debug_assert_eq!(SINGLETON.get().as_str(), "Test");
+
+	SINGLETON.unset();
+	debug_assert!(SINGLETON.is_none());
+
+	SINGLETON.unset();
+	debug_assert!(SINGLETON.is_none());
+}
+
+#[test]
+fn test_singleton_peek() {
+	#[allow(clippy::declare_interior_mutable_const)]
+	const INITIAL: Singleton<u32> = Singleton::new(21);
+	const DOUBLED: u32 = unsafe { *INITIAL.peek() } * 2;
+
+	static SINGLETON: Singleton<u32> = INITIAL;
+	debug_assert_eq!(DOUBLED, 42);
+	debug_assert_eq!(*SINGLETON.get(), 21);
+}
+
+#[test]
+#[should_panic]
+#[cfg(not(feature = "abort-on-violation"))]
+fn test_singleton_uninit_replace_before_init() {
+	static SINGLETON: SingletonUninit<String> = SingletonUninit::uninit();
+	SINGLETON.replace("Hello".to_string());
+}
+
+#[test]
+#[should_panic]
+#[cfg(not(feature = "abort-on-violation"))]
+fn test_singleton_uninit_deinit_before_init() {
+	static SINGLETON: SingletonUninit<String> = SingletonUninit::uninit();
+	SINGLETON.deinit();
+}
+
+struct Pair {
+	left: Vec<u32>,
+	right: Vec<u32>
+}
+
+#[test]
+fn test_guard_map_split() {
+	static SINGLETON: Singleton<Pair> = Singleton::new(Pair { left: Vec::new(), right: Vec::new() });
+
+	let (mut left, mut right) = SinglytonRefMut::map_split(SINGLETON.get_mut(), |pair| (&mut pair.left, &mut pair.right));
+	left.push(1);
+	right.push(2);
+	drop(left);
+	right.push(3);
+	drop(right);
+
+	debug_assert_eq!(SINGLETON.get().left, [1]);
+	debug_assert_eq!(SINGLETON.get().right, [2, 3]);
+}
+
+#[test]
+#[should_panic]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+fn test_guard_map_split_panic() {
+	static SINGLETON: Singleton<Pair> = Singleton::new(Pair { left: Vec::new(), right: Vec::new() });
+
+	let (left, _right) = SinglytonRefMut::map_split(SINGLETON.get_mut(), |pair| (&mut pair.left, &mut pair.right));
+	drop(left);
+	let _ = SINGLETON.get();
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn test_singleton_uninit_init_zeroed() {
+	static SINGLETON: SingletonUninit<[u8; 4096]> = SingletonUninit::uninit();
+
+	SINGLETON.init_zeroed();
+	debug_assert!(SINGLETON.get().iter().all(|byte| *byte == 0));
+
+	SINGLETON.get_mut()[1234] = 42;
+	debug_assert_eq!(SINGLETON.get()[1234], 42);
+}
+
+#[test]
+#[should_panic]
+#[cfg(all(feature = "bytemuck", debug_assertions, not(feature = "abort-on-violation")))]
+fn test_singleton_uninit_init_zeroed_twice() {
+	static SINGLETON: SingletonUninit<[u32; 16]> = SingletonUninit::uninit();
+
+	SINGLETON.init_zeroed();
+	SINGLETON.init_zeroed();
+}
+
+#[test]
+#[cfg(feature = "observers")]
+fn test_singleton_observer() {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+	static NOTIFIED: AtomicUsize = AtomicUsize::new(0);
+	static LAST_SEEN: AtomicUsize = AtomicUsize::new(0);
+
+	*SINGLETON.get_mut() += 1;
+	debug_assert_eq!(NOTIFIED.load(Ordering::SeqCst), 0);
+
+	SINGLETON.add_observer(|value| {
+		NOTIFIED.fetch_add(1, Ordering::SeqCst);
+		LAST_SEEN.store(*value as usize, Ordering::SeqCst);
+	});
+
+	*SINGLETON.get_mut() += 1;
+	debug_assert_eq!(NOTIFIED.load(Ordering::SeqCst), 1);
+	debug_assert_eq!(LAST_SEEN.load(Ordering::SeqCst), 2);
+
+	let _ = SINGLETON.get();
+	debug_assert_eq!(NOTIFIED.load(Ordering::SeqCst), 1);
+
+	{
+		let mut value = SINGLETON.get_mut();
+		*value += 1;
+		*value += 1;
+		debug_assert_eq!(NOTIFIED.load(Ordering::SeqCst), 1);
+	}
+	debug_assert_eq!(NOTIFIED.load(Ordering::SeqCst), 2);
+	debug_assert_eq!(LAST_SEEN.load(Ordering::SeqCst), 4);
+
+	SINGLETON.replace(10);
+	debug_assert_eq!(NOTIFIED.load(Ordering::SeqCst), 3);
+	debug_assert_eq!(LAST_SEEN.load(Ordering::SeqCst), 10);
+}
+
+#[test]
+#[cfg(feature = "once_cell")]
+fn test_singleton_lazy_sync() {
+	use std::sync::{Arc, Barrier, atomic::{AtomicUsize, Ordering}};
+
+	static INITIALIZED: AtomicUsize = AtomicUsize::new(0);
+	static SINGLETON: SingletonLazySync<Vec<u32>> = SingletonLazySync::new(|| {
+		INITIALIZED.fetch_add(1, Ordering::SeqCst);
+		std::thread::sleep(std::time::Duration::from_millis(10));
+		(0..100).collect()
+	});
+
+	debug_assert!(!SINGLETON.is_initialized());
+
+	let barrier = Arc::new(Barrier::new(8));
+	let threads = (0..8).map(|_| {
+		let barrier = barrier.clone();
+		std::thread::spawn(move || {
+			barrier.wait();
+			SINGLETON.get().iter().sum::<u32>()
+		})
+	}).collect::<Vec<_>>();
+
+	for thread in threads {
+		debug_assert_eq!(thread.join().unwrap(), 4950);
+	}
+
+	debug_assert!(SINGLETON.is_initialized());
+	debug_assert_eq!(INITIALIZED.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_singleton_reentrant() {
+	static SINGLETON: SingletonReentrant<Vec<u32>> = SingletonReentrant::new_reentrant(Vec::new());
+
+	fn visit(level: u32) {
+		SINGLETON.with_mut_ptr(|vec| {
+			unsafe { (*vec).push(level) };
+			if level < 4 {
+				visit(level + 1);
+			}
+			unsafe { (*vec).push(level) };
+		});
+	}
+
+	visit(0);
+
+	debug_assert_eq!(SINGLETON.depth(), 0);
+	SINGLETON.with_mut_ptr(|vec| debug_assert_eq!(unsafe { &*vec }, &[0, 1, 2, 3, 4, 4, 3, 2, 1, 0]));
+}
+
+#[test]
+#[should_panic]
+fn test_singleton_reentrant_limited() {
+	static SINGLETON: SingletonReentrant<u32> = SingletonReentrant::new_reentrant_limited(0, 8);
+
+	fn recurse() {
+		SINGLETON.with_mut_ptr(|value| {
+			unsafe { *value += 1 };
+			recurse();
+		});
+	}
+
+	recurse();
+}
+
+#[test]
+fn test_singleton_option_ptr() {
+	static SINGLETON: SingletonOption<u32> = SingletonOption::new_some(21);
+
+	unsafe {
+		let ptr = SINGLETON.as_mut_ptr();
+		*ptr *= 2;
+		debug_assert_eq!(*SINGLETON.as_ptr(), 42);
+	}
+	debug_assert_eq!(*SINGLETON.get(), 42);
+}
+
+#[test]
+#[should_panic]
+fn test_singleton_option_ptr_none() {
+	static SINGLETON: SingletonOption<u32> = SingletonOption::new();
+	let _ = unsafe { SINGLETON.as_ptr() };
+}
+
+#[test]
+#[cfg(all(not(debug_assertions), not(any(feature = "observers", feature = "sync", feature = "spin"))))]
+fn test_singleton_layout() {
+	use core::mem::{align_of, size_of};
+
+	#[repr(C)]
+	struct Embedded {
+		id: u8,
+		value: Singleton<u64>
+	}
+
+	#[repr(C)]
+	struct Plain {
+		id: u8,
+		value: u64
+	}
+
+	assert_eq!(size_of::<Singleton<u64>>(), size_of::<u64>());
+	assert_eq!(align_of::<Singleton<u64>>(), align_of::<u64>());
+	assert_eq!(size_of::<Singleton<[u8; 3]>>(), size_of::<[u8; 3]>());
+	assert_eq!(size_of::<Embedded>(), size_of::<Plain>());
+
+	static EMBEDDED: Embedded = Embedded { id: 1, value: Singleton::new(42) };
+	assert_eq!(EMBEDDED.value.as_raw() as usize, &EMBEDDED.value as *const _ as usize);
+	assert_eq!(unsafe { *EMBEDDED.value.as_raw() }, 42);
+	assert_eq!(EMBEDDED.id, 1);
+}
+
+#[test]
+fn test_singleton_uninit_get_or_try_init() {
+	static SINGLETON: SingletonUninit<u32> = SingletonUninit::uninit();
+
+	debug_assert_eq!(SINGLETON.get_or_try_init(|| "not a number".parse::<u32>()).map(|value| *value).ok(), None);
+	debug_assert_eq!(SINGLETON.get_or_try_init(|| "42".parse::<u32>()).map(|value| *value), Ok(42));
+	debug_assert_eq!(*SINGLETON.get_or_try_init::<(), _>(|| unreachable!()).unwrap(), 42);
+	debug_assert_eq!(*SINGLETON.get(), 42);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn test_singleton_get_mut_blocking() {
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+
+	let (acquired, on_acquired) = std::sync::mpsc::channel();
+	let thread = std::thread::spawn(move || {
+		let mut guard = SINGLETON.get_mut();
+		acquired.send(()).unwrap();
+		std::thread::sleep(std::time::Duration::from_millis(10));
+		*guard += 1;
+	});
+
+	on_acquired.recv().unwrap();
+	*SINGLETON.get_mut_blocking(u32::MAX) += 1;
+	thread.join().unwrap();
+
+	debug_assert_eq!(*SINGLETON.get(), 2);
+}
+
+#[test]
+#[should_panic]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+fn test_singleton_get_mut_blocking_panic() {
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+
+	let _guard = SINGLETON.get();
+	let _ = SINGLETON.get_mut_blocking(100);
+}
+
+#[test]
+fn test_singleton_option_take_or() {
+	static SINGLETON: SingletonOption<String> = SingletonOption::new_some(String::new());
+
+	SINGLETON.get_mut().push_str("Hello");
+	debug_assert_eq!(SINGLETON.take_or("Default".to_string()), "Hello");
+	debug_assert!(SINGLETON.is_none());
+
+	debug_assert_eq!(SINGLETON.take_or("Default".to_string()), "Default");
+	debug_assert!(SINGLETON.is_none());
+}
+
+mod lock_contract {
+	use crate::lock::*;
+
+	// Every lock must allow this sequence of borrows. Checked locks must also refuse conflicting borrows.
+	fn contract<L: BorrowLock>(checked: bool) {
+		let lock = L::INIT;
+
+		{
+			let _a = lock.borrow(None);
+			let _b = lock.borrow(None);
+			assert!(lock.try_borrow().is_some());
+			assert_eq!(lock.try_borrow_mut().is_none(), checked);
+		}
+
+		{
+			let borrow = lock.borrow_mut(None);
+			assert_eq!(lock.try_borrow().is_none(), checked);
+			assert_eq!(lock.try_borrow_mut().is_none(), checked);
+
+			let (a, b) = L::split(borrow);
+			let (b, c) = L::split(b);
+			drop(a);
+			drop(c);
+			assert_eq!(lock.try_borrow().is_none(), checked);
+			drop(b);
+		}
+
+		assert!(lock.try_borrow_mut().is_some());
+		assert!(lock.try_borrow().is_some());
+		let _a = lock.borrow_mut(None);
+	}
+
+	#[test]
+	fn test_lock_unchecked() {
+		contract::<Unchecked>(false);
+	}
+
+	#[test]
+	fn test_lock_borrow_flag() {
+		contract::<BorrowFlag>(true);
+	}
+
+	#[test]
+	fn test_lock_spin() {
+		contract::<SpinLock>(true);
+	}
+
+	#[test]
+	fn test_lock_rwlock() {
+		contract::<std::sync::RwLock<()>>(true);
+	}
+}
+
+#[test]
+fn test_singleton_option_inspect() {
+	static SINGLETON: SingletonOption<u32> = SingletonOption::new();
+
+	let mut calls = 0;
+	SINGLETON.inspect(|_| calls += 1).inspect_mut(|_| calls += 1);
+	debug_assert_eq!(calls, 0);
+
+	SINGLETON.set(1);
+	SINGLETON
+		.inspect(|value| debug_assert_eq!(*value, 1))
+		.inspect_mut(|value| *value += 1)
+		.inspect_mut(|value| *value *= 10)
+		.inspect(|value| debug_assert_eq!(*value, 20));
+
+	debug_assert_eq!(*SINGLETON.get_mut(), 20);
+}
+
+#[test]
+fn test_singleton_reset() {
+	static SINGLETON: Singleton<Vec<u32>> = Singleton::new(Vec::new());
+
+	SINGLETON.get_mut().extend([1, 2, 3]);
+	debug_assert_eq!(SINGLETON.reset(), [1, 2, 3]);
+	debug_assert_eq!(*SINGLETON.get(), Vec::<u32>::default());
+}
+
+#[test]
+fn test_singleton_try_as_ptr() {
+	static SINGLETON: Singleton<u32> = Singleton::new(21);
+
+	unsafe {
+		*SINGLETON.try_as_mut_ptr().unwrap() *= 2;
+		debug_assert_eq!(*SINGLETON.try_as_ptr().unwrap(), 42);
+
+		#[cfg(any(debug_assertions, feature = "sync", feature = "spin"))] {
+			let guard = SINGLETON.get_mut();
+			debug_assert!(SINGLETON.try_as_ptr().is_none());
+			debug_assert!(SINGLETON.try_as_mut_ptr().is_none());
+			drop(guard);
+
+			let guard = SINGLETON.get();
+			debug_assert!(SINGLETON.try_as_ptr().is_some());
+			debug_assert!(SINGLETON.try_as_mut_ptr().is_none());
+			drop(guard);
+		}
+	}
+}
+
+#[test]
+fn test_singleton_lazy() {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	static INITIALIZED: AtomicUsize = AtomicUsize::new(0);
+	static SINGLETON: SingletonLazy<Vec<u32>> = SingletonLazy::new(|| {
+		INITIALIZED.fetch_add(1, Ordering::SeqCst);
+		vec![1, 2, 3]
+	});
+
+	debug_assert!(!SINGLETON.is_initialized());
+	SINGLETON.get_mut().push(4);
+	debug_assert_eq!(*SINGLETON.get(), [1, 2, 3, 4]);
+	debug_assert!(SINGLETON.is_initialized());
+	debug_assert_eq!(INITIALIZED.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_singleton_lazy_pin() {
+	use core::{marker::PhantomPinned, pin::Pin};
+
+	struct SelfAware {
+		address: usize,
+		_pinned: PhantomPinned
+	}
+	impl SelfAware {
+		fn record(self: Pin<&mut Self>) {
+			let this = unsafe { self.get_unchecked_mut() };
+			this.address = this as *const Self as usize;
+		}
+
+		fn is_at_recorded_address(self: Pin<&Self>) -> bool {
+			self.address == &*self as *const Self as usize
+		}
+	}
+
+	static SINGLETON: SingletonLazy<SelfAware> = SingletonLazy::new(|| SelfAware { address: 0, _pinned: PhantomPinned });
+
+	SINGLETON.get_pin_mut().as_mut().record();
+	debug_assert!(SINGLETON.get_pin().as_ref().is_at_recorded_address());
+	debug_assert_ne!(SINGLETON.get().address, 0);
+}
+
+#[test]
+fn test_singleton_take() {
+	static EVENTS: Singleton<Vec<&'static str>> = Singleton::new(Vec::new());
+
+	EVENTS.get_mut().push("Hello");
+	EVENTS.get_mut().push("Test");
+
+	debug_assert_eq!(EVENTS.take(), ["Hello", "Test"]);
+	debug_assert!(EVENTS.get().is_empty());
+	debug_assert!(EVENTS.take().is_empty());
+}
+
+#[test]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+fn test_named_singleton() {
+	named_singleton!(NAMED: u32 = 0);
+
+	let _guard = NAMED.get_mut();
+	let payload = std::panic::catch_unwind(|| {
+		let _ = NAMED.get();
+	}).unwrap_err();
+
+	let message = match payload.downcast::<String>() {
+		Ok(message) => *message,
+		Err(payload) => payload.downcast::<BorrowViolation>().unwrap().to_string()
+	};
+	debug_assert!(message.contains("singlyton::tests::NAMED"), "{}", message);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_singleton_option_entry_or_insert_with() {
+	use std::collections::HashMap;
+
+	static SINGLETON: SingletonOption<HashMap<&'static str, Vec<u32>>> = SingletonOption::new();
+
+	SINGLETON.entry_or_insert_with("a", Vec::new).push(1);
+	let _ = SINGLETON.entry_or_insert_with("b", || vec![10]);
+	SINGLETON.entry_or_insert_with("a", || unreachable!()).push(2);
+
+	debug_assert_eq!(SINGLETON.get().len(), 2);
+	debug_assert_eq!(SINGLETON.get()["a"], [1, 2]);
+	debug_assert_eq!(SINGLETON.get()["b"], [10]);
+}
+
+#[test]
+fn test_singleton_get_or_else() {
+	static SINGLETON: Singleton<u32> = Singleton::new(42);
+
+	debug_assert_eq!(SINGLETON.get_or_else(|| 0, |value| *value), 42);
+
+	#[cfg(any(debug_assertions, feature = "sync", feature = "spin"))] {
+		let guard = SINGLETON.get_mut();
+		debug_assert_eq!(SINGLETON.get_or_else(|| 0, |value| *value), 0);
+		drop(guard);
+	}
+
+	debug_assert_eq!(SINGLETON.get_or_else(|| 0, |value| *value), 42);
+}
+
+#[test]
+fn test_singleton_uninit_init_with() {
+	const LEN: usize = 1 << 16;
+	static SINGLETON: SingletonUninit<[u32; LEN]> = SingletonUninit::uninit();
+
+	unsafe {
+		SINGLETON.init_with(|maybe_uninit| {
+			let ptr = maybe_uninit.as_mut_ptr() as *mut u32;
+			for i in 0..LEN {
+				ptr.add(i).write(i as u32);
+			}
+		});
+	}
+
+	debug_assert!(SINGLETON.get().iter().enumerate().all(|(i, value)| *value == i as u32));
+}
+
+#[test]
+fn test_singleton_ptr_eq() {
+	static A: Singleton<u32> = Singleton::new(0);
+	static B: Singleton<u32> = Singleton::new(0);
+
+	let _guard = A.get_mut();
+	debug_assert!(Singleton::ptr_eq(&A, &A));
+	debug_assert!(!Singleton::ptr_eq(&A, &B));
+}
+
+#[test]
+fn test_singleton_option_flatten_get() {
+	static SINGLETON: SingletonOption<Option<u32>> = SingletonOption::new();
+
+	debug_assert!(SINGLETON.flatten_get().is_none());
+
+	SINGLETON.set(None);
+	debug_assert!(SINGLETON.flatten_get().is_none());
+
+	SINGLETON.set(Some(42));
+	debug_assert_eq!(SINGLETON.flatten_get().as_deref(), Some(&42));
+
+	// The borrow is released on every path
+	let _ = SINGLETON.get_mut();
+}
+
+#[test]
+fn test_replace_drops_outside_borrow() {
+	struct ReadsOnDrop(u32);
+	impl Drop for ReadsOnDrop {
+		fn drop(&mut self) {
+			let _ = SINGLETON.get();
+			let _ = UNINIT.get();
+			let _ = OPTION.is_some();
+		}
+	}
+
+	static SINGLETON: Singleton<ReadsOnDrop> = Singleton::new(ReadsOnDrop(0));
+	static UNINIT: SingletonUninit<ReadsOnDrop> = SingletonUninit::new(ReadsOnDrop(0));
+	static OPTION: SingletonOption<ReadsOnDrop> = SingletonOption::new();
+
+	debug_assert_eq!(SINGLETON.replace(ReadsOnDrop(1)).0, 0);
+	debug_assert_eq!(UNINIT.replace(ReadsOnDrop(1)).0, 0);
+	OPTION.set(ReadsOnDrop(0));
+	OPTION.set(ReadsOnDrop(1));
+	debug_assert_eq!(OPTION.replace(ReadsOnDrop(2)).map(|old| old.0), Some(1));
+
+	debug_assert_eq!(SINGLETON.get().0, 1);
+	debug_assert_eq!(UNINIT.get().0, 1);
+	debug_assert_eq!(OPTION.get().0, 2);
+}
+
+#[test]
+fn test_singleton_option_as_slice() {
+	static SINGLETON: SingletonOption<Vec<u8>> = SingletonOption::new();
+
+	SINGLETON.set(vec![1, 2, 3]);
+	debug_assert_eq!(&*SINGLETON.as_slice(), &[1, 2, 3]);
+
+	SINGLETON.as_mut_slice().reverse();
+	SINGLETON.as_mut_slice()[0] = 4;
+	debug_assert_eq!(&*SINGLETON.as_slice(), &[4, 2, 1]);
+}
+
+#[test]
+#[should_panic]
+fn test_singleton_option_as_slice_none() {
+	static SINGLETON: SingletonOption<Vec<u8>> = SingletonOption::new();
+	let _ = SINGLETON.as_slice();
+}
+
+#[test]
+fn test_singleton_swap_local() {
+	static SINGLETON: Singleton<String> = Singleton::new(String::new());
+	SINGLETON.replace("Global".to_string());
+
+	let mut local = "Local".to_string();
+	SINGLETON.swap_local(&mut local);
+	debug_assert_eq!(local, "Global");
+	debug_assert_eq!(*SINGLETON.get(), "Local");
+
+	local.push_str(" (processed)");
+	SINGLETON.swap_local(&mut local);
+	debug_assert_eq!(local, "Local");
+	debug_assert_eq!(*SINGLETON.get(), "Global (processed)");
+}
+
+#[test]
+fn test_global_state() {
+	struct Config {
+		verbose: bool
+	}
+	global_state!(Config);
+
+	Config::initialize(Config { verbose: false });
+	debug_assert!(!Config::instance().get().verbose);
+
+	Config::instance().get_mut().verbose = true;
+	debug_assert!(Config::instance().get().verbose);
+
+	Config::shutdown();
+	Config::initialize(Config { verbose: false });
+	debug_assert!(!Config::instance().get().verbose);
+}
+
+#[test]
+fn test_leak() {
+	static SINGLETON: Singleton<u32> = Singleton::new(42);
+	static SINGLETON_MUT: Singleton<u32> = Singleton::new(0);
+
+	let leaked: &'static u32 = {
+		let guard = SINGLETON.get();
+		SinglytonRef::leak(guard)
+	};
+	debug_assert_eq!(*leaked, 42);
+	debug_assert_eq!(*SINGLETON.get(), 42);
+
+	let leaked_mut: &'static mut u32 = SinglytonRefMut::leak_mut(SINGLETON_MUT.get_mut());
+	*leaked_mut += 1;
+	debug_assert_eq!(*leaked_mut, 1);
+}
+
+#[test]
+#[should_panic]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+fn test_leak_panic() {
+	static SINGLETON: Singleton<u32> = Singleton::new(42);
+	let _ = SinglytonRef::leak(SINGLETON.get());
+	let _ = SINGLETON.get_mut();
+}
+
+#[test]
+fn test_singleton_uninit_as_uninit_mut_ptr() {
+	#[repr(C)]
+	struct Point {
+		x: i32,
+		y: i32
+	}
+
+	static SINGLETON: SingletonUninit<Point> = SingletonUninit::uninit();
+
+	unsafe {
+		let ptr = SINGLETON.as_uninit_mut_ptr() as *mut Point;
+		core::ptr::addr_of_mut!((*ptr).x).write(1);
+		core::ptr::addr_of_mut!((*ptr).y).write(2);
+		SINGLETON.assume_init();
+	}
+
+	debug_assert_eq!(SINGLETON.get().x, 1);
+	debug_assert_eq!(SINGLETON.get().y, 2);
+}
+
+#[test]
+fn test_singleton_map_or() {
+	struct Stats {
+		hits: u32,
+		_misses: u32
+	}
+
+	static SINGLETON: Singleton<Stats> = Singleton::new(Stats { hits: 0, _misses: 0 });
+
+	SINGLETON.get_mut().hits += 3;
+	debug_assert_eq!(SINGLETON.map_or(0, |stats| stats.hits), 3);
+
+	#[cfg(any(debug_assertions, feature = "sync", feature = "spin"))] {
+		let guard = SINGLETON.get_mut();
+		debug_assert_eq!(SINGLETON.map_or(0, |stats| stats.hits), 0);
+		drop(guard);
+	}
+
+	let _ = SINGLETON.get_mut();
+}
+
+#[test]
+fn test_singleton_option_map_or_else() {
+	static SINGLETON: SingletonOption<String> = SingletonOption::new();
+
+	debug_assert_eq!(SINGLETON.map_or_else(|| 0, String::len), 0);
+
+	// The borrow is released before the default is computed
+	debug_assert!(SINGLETON.map_or_else(|| SINGLETON.as_option_mut().is_none(), |_| false));
+
+	SINGLETON.set("Hello".to_string());
+	debug_assert_eq!(SINGLETON.map_or_else(|| 0, String::len), 5);
+
+	let _ = SINGLETON.get_mut();
+}
+
+#[test]
+fn test_replace_inner() {
+	static SINGLETON: Singleton<String> = Singleton::new(String::new());
+
+	let mut guard = SINGLETON.get_mut();
+	guard.push_str("Old");
+	debug_assert_eq!(guard.replace_inner("New".to_string()), "Old");
+	debug_assert_eq!(*guard, "New");
+	drop(guard);
+
+	debug_assert_eq!(*SINGLETON.get(), "New");
+}
+
+#[test]
+#[cfg(all(feature = "alloc", not(any(feature = "sync", feature = "spin"))))]
+fn test_singleton_get_rc() {
+	use std::rc::Rc;
+
+	// `Rc::new` is not const, so leak the singleton to get a `'static` one
+	let singleton: &'static Singleton<Rc<String>> = Box::leak(Box::new(Singleton::new(Rc::new("Hello".to_string()))));
+
+	let rc = singleton.get_rc();
+	debug_assert_eq!(*rc, "Hello");
+	debug_assert_eq!(Rc::strong_count(&rc), 2);
+
+	// The borrow was released, so the singleton can be mutated while the clone is held
+	*singleton.get_mut() = Rc::new("World".to_string());
+	debug_assert_eq!(*rc, "Hello");
+	debug_assert_eq!(Rc::strong_count(&rc), 1);
+
+	static UNINIT: SingletonUninit<Rc<String>> = SingletonUninit::uninit();
+	UNINIT.init(Rc::new("Hello".to_string()));
+	debug_assert_eq!(*UNINIT.get_rc(), "Hello");
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_singleton_get_arc() {
+	use std::sync::Arc;
+
+	static SINGLETON: SingletonUninit<Arc<String>> = SingletonUninit::uninit();
+	SINGLETON.init(Arc::new("Hello".to_string()));
+
+	let arc = SINGLETON.get_arc();
+	debug_assert_eq!(*arc, "Hello");
+	debug_assert_eq!(Arc::strong_count(&arc), 2);
+
+	let _ = SINGLETON.get_mut();
+}
+
+#[test]
+fn test_singleton_lazy_reads_other_singleton() {
+	static BASE: Singleton<u32> = Singleton::new(20);
+	static SINGLETON: SingletonLazy<u32> = SingletonLazy::new(|| *BASE.get() + 1);
+
+	debug_assert_eq!(*SINGLETON.get(), 21);
+}
+
+#[test]
+#[should_panic(expected = "recursive lazy initialization")]
+#[cfg(not(any(feature = "sync", feature = "spin", feature = "structured-panic", feature = "abort-on-violation")))]
+fn test_singleton_lazy_recursive() {
+	static SINGLETON: SingletonLazy<u32> = SingletonLazy::new(|| *SINGLETON.get() + 1);
+	let _ = SINGLETON.get();
+}
+
+#[test]
+#[cfg(not(any(feature = "sync", feature = "spin", feature = "structured-panic", feature = "abort-on-violation")))]
+fn test_singleton_lazy_poisoned() {
+	static SINGLETON: SingletonLazy<u32> = SingletonLazy::new(|| panic!("initializer failed"));
+
+	assert!(std::panic::catch_unwind(|| *SINGLETON.get()).is_err());
+
+	let message = std::panic::catch_unwind(|| *SINGLETON.get()).unwrap_err();
+	assert_eq!(message.downcast_ref::<String>().map(String::as_str), Some("This singleton's initializer previously panicked"));
+}
+
+#[test]
+fn test_singleton_option_get_or_try_insert_with() {
+	static SINGLETON: SingletonOption<u32> = SingletonOption::new();
+
+	debug_assert_eq!(SINGLETON.get_or_try_insert_with(|| Err("unavailable")).err(), Some("unavailable"));
+	debug_assert!(SINGLETON.is_none());
+
+	*SINGLETON.get_or_try_insert_with(|| Ok::<_, &str>(1)).unwrap() += 1;
+	debug_assert_eq!(*SINGLETON.get(), 2);
+
+	// The closure does not run once the singleton is `Some`
+	let guard = SINGLETON.get_or_try_insert_with::<(), _>(|| unreachable!()).unwrap();
+	debug_assert_eq!(*guard, 2);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_singleton_boxed() {
+	const LEN: usize = 1_000_000;
+	static SINGLETON: SingletonBoxed<[u8; LEN]> = SingletonBoxed::new();
+
+	debug_assert!(core::mem::size_of_val(&SINGLETON) < LEN);
+	#[cfg(all(not(debug_assertions), not(any(feature = "sync", feature = "spin"))))]
+	assert_eq!(core::mem::size_of_val(&SINGLETON), core::mem::size_of::<usize>());
+
+	debug_assert!(!SINGLETON.is_initialized());
+	SINGLETON.init_boxed(vec![0; LEN].into_boxed_slice().try_into().unwrap());
+	debug_assert!(SINGLETON.is_initialized());
+
+	SINGLETON.get_mut()[LEN - 1] = 1;
+	debug_assert_eq!(SINGLETON.get().iter().map(|&byte| byte as usize).sum::<usize>(), 1);
+}
+
+#[test]
+#[should_panic]
+#[cfg(all(debug_assertions, feature = "alloc", not(feature = "abort-on-violation")))]
+fn test_singleton_boxed_init_twice() {
+	static SINGLETON: SingletonBoxed<u32> = SingletonBoxed::new();
+	SINGLETON.init(0);
+	SINGLETON.init(1);
+}
+
+#[test]
+fn test_singleton_try_with() {
+	static SINGLETON: Singleton<u32> = Singleton::new(1);
+
+	debug_assert_eq!(SINGLETON.try_with_mut(|value| { *value += 1; *value }).ok(), Some(2));
+	debug_assert_eq!(SINGLETON.try_with(|value| *value).ok(), Some(2));
+
+	#[cfg(any(debug_assertions, feature = "sync", feature = "spin"))] {
+		let guard = SINGLETON.get();
+		let result = SINGLETON.try_with_mut(|_| unreachable!());
+		debug_assert_eq!(result.map_err(|violation| violation.kind), Err::<(), _>(ViolationKind::AlreadyBorrowed));
+		drop(guard);
+
+		let guard = SINGLETON.get_mut();
+		let result = SINGLETON.try_with(|_| unreachable!());
+		debug_assert_eq!(result.map_err(|violation| violation.kind), Err::<(), _>(ViolationKind::AlreadyMutablyBorrowed));
+		drop(guard);
+	}
+
+	debug_assert_eq!(SINGLETON.try_with(|value| *value).ok(), Some(2));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_singleton_option_take_iter() {
+	static SINGLETON: SingletonOption<Vec<u32>> = SingletonOption::new();
+
+	debug_assert_eq!(SINGLETON.take_iter().count(), 0);
+
+	SINGLETON.set(vec![1, 2, 3]);
+	let sum: u32 = SINGLETON.take_iter().inspect(|_| debug_assert!(SINGLETON.is_none())).sum();
+	debug_assert_eq!(sum, 6);
+	debug_assert!(SINGLETON.is_none());
+}
+
+#[test]
+#[cfg(all(debug_assertions, feature = "abort-on-violation", not(any(feature = "sync", feature = "spin"))))]
+fn test_abort_on_violation() {
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+
+	if std::env::var_os("SINGLYTON_TEST_ABORT_CHILD").is_some() {
+		let _guard = SINGLETON.get_mut();
+		let _ = SINGLETON.get();
+		return;
+	}
+
+	let output = std::process::Command::new(std::env::current_exe().unwrap())
+		.args(["--exact", "tests::test_abort_on_violation", "--nocapture"])
+		.env("SINGLYTON_TEST_ABORT_CHILD", "1")
+		.output()
+		.unwrap();
+
+	// The test harness exits with 101 if the test panicked instead
+	debug_assert!(!output.status.success());
+	debug_assert_ne!(output.status.code(), Some(101));
+	#[cfg(unix)]
+	debug_assert_eq!(std::os::unix::process::ExitStatusExt::signal(&output.status), Some(6));
+
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	debug_assert!(stderr.contains("This singleton is already mutably borrowed"), "{}", stderr);
+}
+
+#[test]
+fn test_singleton_array() {
+	static POOL: SingletonArray<Vec<u8>, 4> = SingletonArray::new([Vec::new(), Vec::new(), Vec::new(), Vec::new()]);
+
+	debug_assert_eq!(POOL.len(), 4);
+
+	// Distinct elements can be mutably borrowed at the same time
+	let mut first = POOL.get_mut(0);
+	let mut second = POOL.get_mut(1);
+	first.push(1);
+	second.push(2);
+	debug_assert_eq!(*POOL.get(2), []);
+	drop(first);
+	drop(second);
+
+	debug_assert_eq!(*POOL.get(0), [1]);
+	debug_assert_eq!(*POOL.get(1), [2]);
+}
+
+#[test]
+#[cfg(any(debug_assertions, feature = "sync", feature = "spin"))]
+fn test_singleton_array_try_get() {
+	static POOL: SingletonArray<u32, 2> = SingletonArray::new([0, 0]);
+
+	let _guard = POOL.get_mut(0);
+	debug_assert!(POOL.try_get(0).is_none());
+	debug_assert!(POOL.try_get_mut(0).is_none());
+	debug_assert!(POOL.try_get_mut(1).is_some());
+}
+
+#[test]
+#[should_panic]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+fn test_singleton_array_same_element_panic() {
+	static POOL: SingletonArray<u32, 2> = SingletonArray::new([0, 0]);
+
+	let _first = POOL.get_mut(0);
+	let _second = POOL.get_mut(0);
+}
+
+#[test]
+#[should_panic]
+fn test_singleton_array_out_of_bounds() {
+	static POOL: SingletonArray<u32, 2> = SingletonArray::new([0, 0]);
+	let _ = POOL.get(2);
+}
+
+#[test]
+fn test_singleton_access() {
+	static SINGLETON: Singleton<&'static str> = Singleton::new("42");
+
+	let parse = || SINGLETON.access(|value| value.parse::<u32>());
+	debug_assert_eq!(parse(), Ok(42));
+
+	SINGLETON.replace("forty-two");
+	debug_assert!(parse().is_err());
+
+	// The borrow was released even though `f` failed
+	let _ = SINGLETON.get_mut();
+}
+
+#[test]
+fn test_singleton_uninit_try_get_opt() {
+	static SINGLETON: SingletonUninit<String> = SingletonUninit::uninit();
+
+	debug_assert!(SINGLETON.try_get_opt().is_none());
+
+	SINGLETON.init("Hello".to_string());
+	debug_assert_eq!(SINGLETON.try_get_opt().as_deref().map(String::as_str), Some("Hello"));
+
+	SINGLETON.deinit();
+	debug_assert!(SINGLETON.try_get_opt().is_none());
+}
+
+#[test]
+fn test_guard_display() {
+	static SINGLETON: Singleton<String> = Singleton::new(String::new());
+	SINGLETON.get_mut().push_str("Hello");
+
+	debug_assert_eq!(format!("{}", SINGLETON.get()), "Hello");
+	debug_assert_eq!(format!("{}!", SINGLETON.get_mut()), "Hello!");
+	debug_assert_eq!(format!("{:>6}", SINGLETON.upgradable_read()), " Hello");
+}
+
+#[test]
+fn test_singleton_compare_exchange() {
+	#[derive(Debug, Clone, PartialEq)]
+	enum State {
+		Idle,
+		Running,
+		Stopped
+	}
+
+	static SINGLETON: Singleton<State> = Singleton::new(State::Idle);
+
+	debug_assert_eq!(SINGLETON.compare_exchange(&State::Idle, State::Running), Ok(State::Idle));
+	debug_assert_eq!(*SINGLETON.get(), State::Running);
+
+	debug_assert_eq!(SINGLETON.compare_exchange(&State::Idle, State::Stopped), Err(State::Running));
+	debug_assert_eq!(*SINGLETON.get(), State::Running);
+
+	debug_assert_eq!(SINGLETON.compare_exchange(&State::Running, State::Stopped), Ok(State::Running));
+	debug_assert_eq!(*SINGLETON.get(), State::Stopped);
+}
+
+#[test]
+fn test_singleton_option_replace_rotation() {
+	static SINGLETON: SingletonOption<&'static str> = SingletonOption::new();
+
+	let mut previous = Vec::new();
+	for val in ["first", "second", "third"] {
+		previous.push(SINGLETON.replace(val));
+	}
+
+	debug_assert_eq!(previous, [None, Some("first"), Some("second")]);
+	debug_assert_eq!(*SINGLETON.get(), "third");
+}
+
+#[test]
+fn test_singleton_option_set_drops_after_release() {
+	static SINGLETON: SingletonOption<Resource> = SingletonOption::new();
+	static SEEN: Singleton<Option<u32>> = Singleton::new(None);
+
+	struct Resource(u32);
+	impl Drop for Resource {
+		fn drop(&mut self) {
+			// Would conflict with the borrow if it were still held
+			*SEEN.get_mut() = Some(SINGLETON.get().0);
+		}
+	}
+
+	SINGLETON.set(Resource(1));
+	SINGLETON.set(Resource(2));
+	debug_assert_eq!(*SEEN.get(), Some(2));
+}
+
+#[test]
+#[cfg(not(feature = "abort-on-violation"))]
+fn test_singleton_uninit_lifecycle() {
+	static SINGLETON: SingletonUninit<u32> = SingletonUninit::new(1);
+
+	debug_assert_eq!(*SINGLETON.get(), 1);
+	debug_assert!(SINGLETON.try_init(2).is_err());
+
+	SINGLETON.deinit();
+	debug_assert!(SINGLETON.try_get_opt().is_none());
+	#[cfg(debug_assertions)]
+	assert!(std::panic::catch_unwind(|| *SINGLETON.get()).is_err());
+	assert!(std::panic::catch_unwind(|| SINGLETON.deinit()).is_err());
+
+	SINGLETON.init(3);
+	debug_assert_eq!(*SINGLETON.get(), 3);
+	debug_assert_eq!(SINGLETON.replace(4), 3);
+	#[cfg(debug_assertions)]
+	assert!(std::panic::catch_unwind(|| SINGLETON.init(5)).is_err());
+	debug_assert_eq!(*SINGLETON.get(), 4);
+}
+
+#[test]
+fn test_singleton_option_get_fields() {
+	struct Config {
+		name: &'static str,
+		port: u16,
+		verbose: bool
+	}
+
+	static SINGLETON: SingletonOption<Config> = SingletonOption::new();
+	SINGLETON.set(Config { name: "server", port: 8080, verbose: true });
+
+	let (name, port, verbose) = SINGLETON.get_fields(|config| (config.name, config.port, config.verbose));
+	debug_assert_eq!((name, port, verbose), ("server", 8080, true));
+
+	let _ = SINGLETON.get_mut();
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_singleton_vec() {
+	static SINGLETON: Singleton<Vec<u32>> = Singleton::new(Vec::new());
+
+	debug_assert!(SINGLETON.is_empty());
+	for i in 1..=3 {
+		SINGLETON.push(i);
+	}
+	debug_assert_eq!(SINGLETON.len(), 3);
+
+	debug_assert_eq!(SINGLETON.pop(), Some(3));
+	debug_assert_eq!(SINGLETON.pop(), Some(2));
+	debug_assert_eq!(SINGLETON.len(), 1);
+
+	SINGLETON.clear();
+	debug_assert!(SINGLETON.is_empty());
+	debug_assert_eq!(SINGLETON.pop(), None);
+}
+
+#[test]
+fn test_guard_try_map() {
+	static SINGLETON: Singleton<[u32; 3]> = Singleton::new([1, 2, 3]);
+
+	let second = SinglytonRef::try_map(SINGLETON.get(), |arr| arr.get(1).ok_or("out of bounds")).ok().unwrap();
+	debug_assert_eq!(*second, 2);
+	drop(second);
+
+	// The projection fails, but the original guard is recovered
+	let (whole, err) = match SinglytonRef::try_map(SINGLETON.get(), |arr| arr.get(5).ok_or("out of bounds")) {
+		Ok(_) => unreachable!(),
+		Err(failed) => failed
+	};
+	debug_assert_eq!(err, "out of bounds");
+	debug_assert_eq!(whole.iter().sum::<u32>(), 6);
+	drop(whole);
+
+	SINGLETON.get_mut()[0] = 4;
+}
+
+#[test]
+#[cfg(all(any(debug_assertions, feature = "spin"), not(feature = "sync")))]
+fn test_borrow_count() {
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+	debug_assert_eq!(SINGLETON.immutable_borrow_count(), 0);
+
+	let borrows = [SINGLETON.get(), SINGLETON.get(), SINGLETON.get()];
+	assert_eq!(SINGLETON.immutable_borrow_count(), 3);
+	assert!(!SINGLETON.has_mutable_borrow());
+	drop(borrows);
+
+	let borrow = SINGLETON.get_mut();
+	assert_eq!(SINGLETON.immutable_borrow_count(), 0);
+	assert!(SINGLETON.has_mutable_borrow());
+	drop(borrow);
+
+	assert!(!SINGLETON.has_mutable_borrow());
+}
+
+#[test]
+fn test_singleton_option_extend() {
+	static SINGLETON: SingletonOption<Vec<u32>> = SingletonOption::new();
+
+	(&SINGLETON).extend(0..3);
+	debug_assert_eq!(*SINGLETON.get(), [0, 1, 2]);
+
+	(&SINGLETON).extend(3..5);
+	debug_assert_eq!(*SINGLETON.get(), [0, 1, 2, 3, 4]);
+}
+
+#[test]
+#[cfg(feature = "sync")]
+fn test_sharded_singleton() {
+	static COUNTER: ShardedSingleton<u64, 4> = ShardedSingleton::new([0; 4]);
+	debug_assert_eq!(COUNTER.shards(), 4);
+
+	let threads = (0..8).map(|_| std::thread::spawn(|| {
+		for _ in 0..1000 {
+			*COUNTER.get_mut() += 1;
+		}
+	})).collect::<Vec<_>>();
+	for thread in threads {
+		thread.join().unwrap();
+	}
+
+	assert_eq!(COUNTER.reduce(0, |total, shard| total + shard), 8000);
+	assert_eq!(COUNTER.reduce(0, |used, shard| used + (*shard > 0) as usize), 4);
+}
+
+#[test]
+fn test_singleton_uninit_init_map() {
+	struct Config {
+		port: u16,
+		verbose: bool
+	}
+
+	static CONFIG: SingletonUninit<Config> = SingletonUninit::uninit();
+
+	CONFIG.init_map("port=8080 verbose", |seed| Config {
+		port: seed.split_whitespace().find_map(|arg| arg.strip_prefix("port=")).unwrap().parse().unwrap(),
+		verbose: seed.split_whitespace().any(|arg| arg == "verbose")
+	});
+
+	debug_assert_eq!(CONFIG.get().port, 8080);
+	debug_assert!(CONFIG.get().verbose);
+}
+
+#[test]
+#[should_panic]
+#[cfg(all(debug_assertions, not(feature = "abort-on-violation")))]
+fn test_singleton_uninit_init_map_panic() {
+	static SINGLETON: SingletonUninit<u32> = SingletonUninit::new(0);
+	SINGLETON.init_map(1, |seed| seed + 1);
+}
+
+#[test]
+fn test_singleton_option_new_some_const() {
+	struct Handle(u32);
+	impl Drop for Handle {
+		fn drop(&mut self) {}
+	}
+
+	static NAMES: SingletonOption<Vec<String>> = SingletonOption::new_some(Vec::new());
+	static HANDLE: SingletonOption<Handle> = SingletonOption::new_some(Handle(1));
+	static NESTED: SingletonOption<(Option<String>, [Handle; 2])> = SingletonOption::new_some((None, [Handle(2), Handle(3)]));
+
+	NAMES.get_mut().push("Hello".to_string());
+	debug_assert_eq!(*NAMES.get(), ["Hello"]);
+	debug_assert_eq!(HANDLE.get().0, 1);
+	debug_assert_eq!(NESTED.get().1[1].0, 3);
+}
+
+#[test]
+fn test_guard_index() {
+	use std::collections::HashMap;
+
+	fn first<C: core::ops::Index<usize, Output = u32>>(container: C) -> u32 {
+		container[0]
+	}
+
+	static VEC: Singleton<Vec<u32>> = Singleton::new(Vec::new());
+	static MAP: SingletonOption<HashMap<&'static str, u32>> = SingletonOption::new();
+
+	VEC.get_mut().extend([1, 2, 3]);
+	VEC.get_mut()[1] = 20;
+	debug_assert_eq!(VEC.get()[1], 20);
+	debug_assert_eq!(VEC.get()[1..], [20, 3]);
+	debug_assert_eq!(first(VEC.get()), 1);
+	debug_assert_eq!(first(VEC.get_mut()), 1);
+
+	MAP.get_or_insert_default().insert("Hello", 1);
+	debug_assert_eq!(MAP.get()["Hello"], 1);
+	debug_assert_eq!(MAP.get_mut()["Hello"], 1);
+}
+
+#[test]
+fn test_singleton_snapshot() {
+	static SINGLETON: Singleton<Vec<u32>> = Singleton::new(Vec::new());
+
+	SINGLETON.get_mut().push(1);
+	let snapshot = SINGLETON.snapshot();
+	SINGLETON.get_mut().push(2);
+
+	SINGLETON.restore(snapshot);
+	debug_assert_eq!(*SINGLETON.get(), [1]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_snapshot_history() {
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+
+	let mut history = SnapshotHistory::new(3);
+	let mut frames = Vec::new();
+	for _ in 0..4 {
+		*SINGLETON.get_mut() += 1;
+		frames.push(history.capture(&SINGLETON));
+	}
+
+	// The oldest snapshot has been discarded
+	debug_assert_eq!(history.len(), 3);
+	debug_assert_eq!(history.get(frames[0]), None);
+	assert!(!history.rewind(&SINGLETON, frames[0]));
+	debug_assert_eq!(*SINGLETON.get(), 4);
+
+	// Rewinding discards the snapshots after it
+	assert!(history.rewind(&SINGLETON, frames[1]));
+	debug_assert_eq!(*SINGLETON.get(), 2);
+	debug_assert_eq!(history.latest(), Some(frames[1]));
+	debug_assert_eq!(history.get(frames[3]), None);
+
+	*SINGLETON.get_mut() = 10;
+	let frame = history.capture(&SINGLETON);
+	debug_assert!(frame > frames[3]);
+	assert!(history.rewind(&SINGLETON, frames[1]));
+	debug_assert_eq!(*SINGLETON.get(), 2);
+}
+
+#[test]
+fn test_singleton_lazy_ptr() {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	static INITIALIZED: AtomicUsize = AtomicUsize::new(0);
+	static SINGLETON: SingletonLazy<u32> = SingletonLazy::new(|| {
+		INITIALIZED.fetch_add(1, Ordering::SeqCst);
+		42
+	});
+
+	// Does not run the initializer
+	assert!(unsafe { SINGLETON.try_ptr() }.is_none());
+	assert_eq!(INITIALIZED.load(Ordering::SeqCst), 0);
+
+	// Runs the initializer
+	let ptr = unsafe { SINGLETON.force_ptr() };
+	assert_eq!(INITIALIZED.load(Ordering::SeqCst), 1);
+	assert_eq!(unsafe { *ptr }, 42);
+
+	assert_eq!(unsafe { SINGLETON.try_ptr() }, Some(ptr));
+	assert_eq!(unsafe { SINGLETON.force_ptr() }, ptr);
+	assert_eq!(INITIALIZED.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_singleton_option_get_or_insert_reporting() {
+	static SINGLETON: SingletonOption<Vec<&'static str>> = SingletonOption::new();
+
+	let (mut registry, inserted) = SINGLETON.get_or_insert_reporting(Vec::new);
+	debug_assert!(inserted);
+	registry.push("Hello");
+	drop(registry);
+
+	let (registry, inserted) = SINGLETON.get_or_insert_reporting(|| unreachable!());
+	debug_assert!(!inserted);
+	debug_assert_eq!(*registry, ["Hello"]);
+}
+
+#[test]
+fn test_deferred_singleton() {
+	static SINGLETON: DeferredSingleton<String> = DeferredSingleton::new();
+	debug_assert!(!SINGLETON.is_set());
+
+	SINGLETON.set_once("Hello".to_string());
+	debug_assert!(SINGLETON.is_set());
+	SINGLETON.get_mut().push_str(", world!");
+	debug_assert_eq!(*SINGLETON.get(), "Hello, world!");
+}
+
+#[test]
+#[should_panic(expected = "has not been initialized yet")]
+#[cfg(not(any(feature = "structured-panic", feature = "abort-on-violation")))]
+fn test_deferred_singleton_get_before_set() {
+	static SINGLETON: DeferredSingleton<u32> = DeferredSingleton::new();
+	let _ = SINGLETON.get();
+}
+
+#[test]
+#[should_panic(expected = "has already been initialized")]
+#[cfg(not(any(feature = "structured-panic", feature = "abort-on-violation")))]
+fn test_deferred_singleton_set_twice() {
+	static SINGLETON: DeferredSingleton<u32> = DeferredSingleton::new();
+	SINGLETON.set_once(1);
+	SINGLETON.set_once(2);
+}
+
+#[test]
+fn test_guard_reborrow() {
+	fn total(values: &[u32]) -> u32 {
+		values.iter().sum()
+	}
+
+	static SINGLETON: Singleton<Vec<u32>> = Singleton::new(Vec::new());
+
+	let mut guard = SINGLETON.get_mut();
+	guard.push(1);
+	guard.push(2);
+	debug_assert_eq!(total(guard.reborrow()), 3);
+	guard.push(3);
+	debug_assert_eq!(total(guard.reborrow()), 6);
+}
+
+#[test]
+fn test_singleton_with_async() {
+	use core::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+
+	async fn yield_now() {
+		let mut yielded = false;
+		core::future::poll_fn(|cx| {
+			if yielded {
+				Poll::Ready(())
+			} else {
+				yielded = true;
+				cx.waker().wake_by_ref();
+				Poll::Pending
+			}
+		}).await
+	}
+
+	async fn task() -> u32 {
+		let before = SINGLETON.with_mut(|val| {
+			*val += 1;
+			*val
+		});
+		yield_now().await;
+		before + SINGLETON.with(|val| *val)
+	}
+
+	let mut a = pin!(task());
+	let mut b = pin!(task());
+	let mut cx = Context::from_waker(Waker::noop());
+
+	// Both tasks are suspended at the same time, and neither holds a borrow while suspended
+	debug_assert!(a.as_mut().poll(&mut cx).is_pending());
+	debug_assert!(b.as_mut().poll(&mut cx).is_pending());
+	*SINGLETON.get_mut() += 10;
+	debug_assert_eq!(a.as_mut().poll(&mut cx), Poll::Ready(13));
+	debug_assert_eq!(b.as_mut().poll(&mut cx), Poll::Ready(14));
+}
+
+#[test]
+fn test_singleton_array_iter_occupied() {
+	static EMPTY: SingletonArray<Option<u32>, 0> = SingletonArray::new([]);
+	static POOL: SingletonArray<Option<u32>, 6> = SingletonArray::new([None; 6]);
+	static FULL: SingletonArray<Option<u32>, 3> = SingletonArray::new([Some(1), Some(2), Some(3)]);
+
+	debug_assert_eq!(EMPTY.iter_occupied().count(), 0);
+	debug_assert_eq!(POOL.iter_occupied().count(), 0);
+	debug_assert_eq!(FULL.iter_occupied().count(), 3);
+
+	for index in (0..POOL.len()).step_by(2) {
+		*POOL.get_mut(index) = Some(index as u32);
+	}
+	let occupied = POOL.iter_occupied().map(|slot| *slot).collect::<Vec<_>>();
+	debug_assert_eq!(occupied, [0, 2, 4]);
+
+	// Vacant slots are not borrowed
+	let guards = POOL.iter_occupied().collect::<Vec<_>>();
+	*POOL.get_mut(1) = Some(1);
+	drop(guards);
+}
+
+#[test]
+#[cfg(feature = "observers")]
+fn test_singleton_batch() {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	static NOTIFIED: AtomicUsize = AtomicUsize::new(0);
+	static SINGLETON: Singleton<Vec<u32>> = Singleton::new(Vec::new());
+
+	SINGLETON.add_observer(|_| {
+		NOTIFIED.fetch_add(1, Ordering::SeqCst);
+	});
+
+	SINGLETON.batch(|particles| {
+		for i in 0..10_000 {
+			particles.push(i);
+			// Every write is covered by the same borrow
+			#[cfg(all(any(debug_assertions, feature = "spin"), not(feature = "sync")))]
+			assert!(SINGLETON.has_mutable_borrow());
+		}
+	});
+
+	debug_assert_eq!(SINGLETON.get().len(), 10_000);
+	assert!(!SINGLETON.has_mutable_borrow());
+	assert_eq!(NOTIFIED.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_singleton_option_replace_if_none() {
+	static SINGLETON: SingletonOption<String> = SingletonOption::new();
+
+	assert_eq!(SINGLETON.replace_if_none("Hello".to_string()), Ok(()));
+	assert_eq!(SINGLETON.replace_if_none("World".to_string()), Err("World".to_string()));
+	debug_assert_eq!(*SINGLETON.get(), "Hello");
+
+	SINGLETON.unset();
+	assert_eq!(SINGLETON.replace_if_none("World".to_string()), Ok(()));
+	debug_assert_eq!(*SINGLETON.get(), "World");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_thread_local_singleton() {
+	crate::thread_local_singleton!(static COUNTER: u32 = 0);
+
+	COUNTER.with_mut(|counter| *counter += 1);
+	debug_assert_eq!(COUNTER.get_cloned(), 1);
+
+	let threads = (0..4).map(|i| std::thread::spawn(move || {
+		debug_assert_eq!(COUNTER.get_cloned(), 0);
+		for _ in 0..i {
+			COUNTER.with_mut(|counter| *counter += 1);
+		}
+		COUNTER.with(|counter| *counter)
+	})).collect::<Vec<_>>();
+	let counts = threads.into_iter().map(|thread| thread.join().unwrap()).collect::<Vec<_>>();
+
+	assert_eq!(counts, [0, 1, 2, 3]);
+	assert_eq!(COUNTER.replace(10), 1);
+	assert_eq!(COUNTER.take(), 10);
+	assert_eq!(COUNTER.get_cloned(), 0);
+}
+
+#[test]
+fn test_guard_into_pin() {
+	use core::pin::Pin;
+
+	struct Greeter(&'static str);
+	impl Greeter {
+		fn greet(self: Pin<&Self>) -> &'static str {
+			self.get_ref().0
+		}
+	}
+
+	static SINGLETON: Singleton<Greeter> = Singleton::new(Greeter("Hello"));
+
+	let pinned = SinglytonRef::into_pin(SINGLETON.get());
+	debug_assert_eq!(pinned.as_ref().greet(), "Hello");
+}
+
+#[test]
+#[cfg(not(any(feature = "sync", feature = "spin")))]
+fn test_singleton_project_cell() {
+	use core::cell::{Cell, RefCell};
+
+	struct Registry {
+		hits: Cell<u32>,
+		names: RefCell<Vec<&'static str>>
+	}
+
+	static SINGLETON: Singleton<Registry> = Singleton::new(Registry { hits: Cell::new(0), names: RefCell::new(Vec::new()) });
+
+	let registry = SINGLETON.get();
+	let hits = SINGLETON.project(|registry| &registry.hits);
+	let names = SINGLETON.project(|registry| &registry.names);
+
+	hits.set(hits.get() + 1);
+	names.borrow_mut().push("Hello");
+
+	debug_assert_eq!(registry.hits.get(), 1);
+	debug_assert_eq!(*registry.names.borrow(), ["Hello"]);
+	#[cfg(debug_assertions)]
+	assert_eq!(SINGLETON.immutable_borrow_count(), 3);
+}
+
+#[test]
+fn test_singleton_uninit_init_with_fn() {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	static CONSTRUCTED: AtomicUsize = AtomicUsize::new(0);
+	static SINGLETON: SingletonUninit<Vec<u32>> = SingletonUninit::uninit();
+
+	let construct = || {
+		CONSTRUCTED.fetch_add(1, Ordering::SeqCst);
+		vec![1, 2, 3]
+	};
+
+	SINGLETON.init_with_fn(construct);
+	assert_eq!(CONSTRUCTED.load(Ordering::SeqCst), 1);
+	debug_assert_eq!(*SINGLETON.get(), [1, 2, 3]);
+
+	// Initializing twice panics in every build, before the value is constructed again
+	#[cfg(not(feature = "abort-on-violation"))] {
+		let _ = std::panic::catch_unwind(|| SINGLETON.init_with_fn(construct)).unwrap_err();
+		assert_eq!(CONSTRUCTED.load(Ordering::SeqCst), 1);
+		debug_assert_eq!(*SINGLETON.get(), [1, 2, 3]);
+	}
+}
+
+#[test]
+fn test_singleton_eq_hash() {
+	use core::hash::BuildHasher;
+	use std::collections::hash_map::RandomState;
+
+	static A: Singleton<&'static str> = Singleton::new("Hello");
+	static B: Singleton<&'static str> = Singleton::new("Hello");
+	static C: Singleton<&'static str> = Singleton::new("World");
+
+	debug_assert!(A == B);
+	debug_assert!(A != C);
+	debug_assert!(A == A);
+
+	let hasher = RandomState::new();
+	debug_assert_eq!(hasher.hash_one(&A), hasher.hash_one(&B));
+	debug_assert_eq!(hasher.hash_one(&A), hasher.hash_one("Hello"));
+
+	*C.get_mut() = "Hello";
+	debug_assert!(A == C);
+}
+
+#[test]
+#[should_panic]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+fn test_singleton_eq_panic() {
+	static A: Singleton<u32> = Singleton::new(0);
+	static B: Singleton<u32> = Singleton::new(0);
+
+	let _guard = B.get_mut();
+	let _ = A == B;
+}
+
+#[test]
+fn test_singleton_option_get_unchecked() {
+	static SINGLETON: SingletonOption<u32> = SingletonOption::new_some(1);
+
+	unsafe {
+		*SINGLETON.get_mut_unchecked() += 1;
+		debug_assert_eq!(*SINGLETON.get_unchecked(), 2);
+	}
+	debug_assert_eq!(*SINGLETON.get(), 2);
+}
+
+#[test]
+#[should_panic]
+#[cfg(debug_assertions)]
+fn test_singleton_option_get_unchecked_panic() {
+	static SINGLETON: SingletonOption<u32> = SingletonOption::new();
+	let _ = unsafe { SINGLETON.get_unchecked() };
+}
+
+#[test]
+fn test_singleton_from() {
+	let singleton: &'static Singleton<Vec<u32>> = Box::leak(Box::new(vec![1, 2, 3].into()));
+	singleton.get_mut().push(4);
+	debug_assert_eq!(*singleton.get(), [1, 2, 3, 4]);
+}
+
+#[test]
+fn test_singleton_lazy_set() {
+	use std::sync::atomic::{AtomicUsize, Ordering};
+
+	static INITIALIZED: AtomicUsize = AtomicUsize::new(0);
+	static SINGLETON: SingletonLazy<String> = SingletonLazy::new(|| {
+		INITIALIZED.fetch_add(1, Ordering::SeqCst);
+		"expensive".to_string()
+	});
+
+	assert_eq!(SINGLETON.set("injected".to_string()), Ok(()));
+	debug_assert_eq!(*SINGLETON.get(), "injected");
+	assert_eq!(SINGLETON.set("again".to_string()), Err("again".to_string()));
+	debug_assert_eq!(*SINGLETON.get(), "injected");
+	assert_eq!(INITIALIZED.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_guard_into_iter() {
+	static SINGLETON: Singleton<Vec<u32>> = Singleton::new(Vec::new());
+	SINGLETON.get_mut().extend([1, 2, 3]);
+
+	let mut total = 0;
+	for val in &SINGLETON.get() {
+		total += val;
+	}
+	debug_assert_eq!(total, 6);
+
+	for val in &mut SINGLETON.get_mut() {
+		*val *= 2;
+	}
+	let guard = SINGLETON.get_mut();
+	debug_assert_eq!((&guard).into_iter().copied().collect::<Vec<_>>(), [2, 4, 6]);
+}
+
+#[test]
+#[cfg(all(debug_assertions, feature = "backtrace", not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+fn test_borrow_backtrace() {
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+
+	#[inline(never)]
+	fn hold_borrow_elsewhere() -> SinglytonRef<'static, u32> {
+		SINGLETON.get()
+	}
+
+	let guard = hold_borrow_elsewhere();
+	let payload = std::panic::catch_unwind(|| *SINGLETON.get_mut() += 1).unwrap_err();
+	let message = match payload.downcast::<BorrowViolation>() {
+		Ok(violation) => violation.to_string(),
+		Err(payload) => *payload.downcast::<String>().unwrap()
+	};
+	assert!(message.contains("The conflicting borrow was acquired at"));
+	assert!(message.contains("hold_borrow_elsewhere"));
+	drop(guard);
+
+	// The backtrace is forgotten once the borrow is released
+	let guard = SINGLETON.get_mut();
+	let payload = std::panic::catch_unwind(|| *SINGLETON.get()).unwrap_err();
+	let message = match payload.downcast::<BorrowViolation>() {
+		Ok(violation) => violation.to_string(),
+		Err(payload) => *payload.downcast::<String>().unwrap()
+	};
+	assert!(!message.contains("hold_borrow_elsewhere"));
+	drop(guard);
+}
+
+#[test]
+fn test_with_both() {
+	static PRICE: Singleton<u32> = Singleton::new(3);
+	static QUANTITY: Singleton<u32> = Singleton::new(4);
+	static NAME: Singleton<&'static str> = Singleton::new("Apples");
+
+	debug_assert_eq!(with_both(&PRICE, &QUANTITY, |price, quantity| price * quantity), 12);
+	debug_assert_eq!(with_both(&NAME, &QUANTITY, |name, quantity| format!("{} x{}", name, quantity)), "Apples x4");
+	debug_assert_eq!(with_both(&PRICE, &PRICE, |a, b| a + b), 6);
+
+	with_both_mut(&PRICE, &QUANTITY, core::mem::swap);
+	debug_assert_eq!((*PRICE.get(), *QUANTITY.get()), (4, 3));
+}
+
+#[test]
+#[should_panic]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+fn test_with_both_mut_same_panic() {
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+	with_both_mut(&SINGLETON, &SINGLETON, |a, b| *a += *b);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn test_singleton_option_atomic() {
+	use std::sync::{Arc, Barrier};
+
+	static SINGLETON: SingletonOptionAtomic<(u32, u32)> = SingletonOptionAtomic::new();
+	assert!(!SINGLETON.is_some());
+	assert!(SINGLETON.get().is_none());
+
+	let barrier = Arc::new(Barrier::new(5));
+	let readers = (0..4).map(|_| {
+		let barrier = barrier.clone();
+		std::thread::spawn(move || {
+			// Every reader polls before the value is set
+			let polled_before = !SINGLETON.is_some();
+			barrier.wait();
+			loop {
+				if let Some(val) = SINGLETON.get() {
+					assert_eq!(*val, (1, 2));
+					return polled_before;
+				}
+				std::hint::spin_loop();
+			}
+		})
+	}).collect::<Vec<_>>();
+
+	barrier.wait();
+	assert_eq!(SINGLETON.set(Box::new((1, 2))), Ok(()));
+	assert_eq!(SINGLETON.set(Box::new((3, 4))), Err(Box::new((3, 4))));
+
+	for reader in readers {
+		assert!(reader.join().unwrap());
+	}
+	assert_eq!(SINGLETON.get(), Some(&(1, 2)));
+}
+
+#[test]
+fn test_singleton_replace_get_mut() {
+	static SINGLETON: Singleton<Vec<u32>> = Singleton::new(Vec::new());
+	SINGLETON.get_mut().push(1);
+
+	let mut guard = SINGLETON.replace_get_mut(vec![10]);
+	guard.push(20);
+	guard.push(30);
+	drop(guard);
+
+	debug_assert_eq!(*SINGLETON.get(), [10, 20, 30]);
 }
\ No newline at end of file
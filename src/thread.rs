@@ -0,0 +1,26 @@
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+static PROVIDER: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Sets the function used to identify the current thread, which enables thread checks in debug builds.
+///
+/// Singletons do not identify threads by themselves, as doing so requires thread-local storage, which is not available in `no_std` environments and can recurse inside a global allocator. Instead, the embedding application can supply its own thread ids, such as from an RTOS task handle or the `TPIDR` register on ARM.
+///
+/// Once a provider is set, each singleton belongs to the first thread that borrows it, and borrowing it from any other thread will panic. Thread ids only need to be unique between threads that are alive at the same time.
+///
+/// This has no effect in release builds, or with the `sync` or `spin` features, as singletons can be shared between threads with them.
+pub fn set_thread_id_provider(provider: fn() -> usize) {
+	PROVIDER.store(provider as *mut (), Ordering::Release);
+}
+
+#[inline]
+#[cfg(any(test, all(not(feature = "sync"), any(debug_assertions, feature = "spin"))))]
+/// The id of the current thread, if a provider has been set.
+pub(crate) fn current() -> Option<usize> {
+	let provider = PROVIDER.load(Ordering::Acquire);
+	if provider.is_null() {
+		None
+	} else {
+		Some(unsafe { core::mem::transmute::<*mut (), fn() -> usize>(provider) }())
+	}
+}
\ No newline at end of file
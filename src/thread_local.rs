@@ -0,0 +1,79 @@
+use std::thread::LocalKey;
+use crate::Singleton;
+
+/// A global singleton with a separate instance for each thread, declared with [`thread_local_singleton!`](crate::thread_local_singleton).
+///
+/// Each thread lazily creates its own instance on first access, which is dropped when the thread exits. Threads can never observe each other's instances, so they never conflict, and there is no need for thread checks.
+///
+/// The singleton is accessed through closures rather than guards, as a guard could outlive the thread's instance. The borrow is released before each accessor returns.
+///
+/// Requires the `std` feature.
+///
+/// # Panics
+///
+/// In debug builds, usage of this abstraction is checked for safety at runtime.
+///
+/// * Mixing mutabilty of borrows within the same thread will panic
+///
+/// Accessing the singleton while the thread is being torn down, such as from the `Drop` implementation of another thread-local, will always panic.
+pub struct ThreadLocalSingleton<T: 'static>(&'static LocalKey<Singleton<T>>);
+
+impl<T: 'static> ThreadLocalSingleton<T> {
+	#[inline]
+	#[doc(hidden)]
+	pub const fn new(key: &'static LocalKey<Singleton<T>>) -> Self {
+		Self(key)
+	}
+
+	#[inline]
+	/// Calls `f` with an **immutable reference** to this thread's instance and returns its result.
+	///
+	/// In debug builds, this will panic if a mutable reference is currently held.
+	pub fn with<R, F>(&'static self, f: F) -> R
+	where
+		F: FnOnce(&T) -> R
+	{
+		self.0.with(|singleton| f(&singleton.0.get()))
+	}
+
+	#[inline]
+	/// Calls `f` with a **mutable reference** to this thread's instance and returns its result.
+	///
+	/// In debug builds, this will panic if an existing mutable or immutable reference is currently held.
+	pub fn with_mut<R, F>(&'static self, f: F) -> R
+	where
+		F: FnOnce(&mut T) -> R
+	{
+		self.0.with(|singleton| f(&mut singleton.0.get_mut()))
+	}
+
+	#[inline]
+	/// Returns a clone of this thread's instance.
+	///
+	/// In debug builds, this will panic if a mutable reference is currently held.
+	pub fn get_cloned(&'static self) -> T
+	where
+		T: Clone
+	{
+		self.with(T::clone)
+	}
+
+	#[inline]
+	/// Replaces this thread's instance with anew, returning the previous value.
+	///
+	/// In debug builds, this will panic if an existing mutable or immutable reference is currently held.
+	pub fn replace(&'static self, val: T) -> T {
+		self.with_mut(|current| core::mem::replace(current, val))
+	}
+
+	#[inline]
+	/// Takes the value out of this thread's instance, leaving `T::default()` in its place.
+	///
+	/// In debug builds, this will panic if an existing mutable or immutable reference is currently held.
+	pub fn take(&'static self) -> T
+	where
+		T: Default
+	{
+		self.with_mut(core::mem::take)
+	}
+}
\ No newline at end of file
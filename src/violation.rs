@@ -0,0 +1,112 @@
+use core::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// The kind of misuse detected by a singleton's checks.
+pub enum ViolationKind {
+	/// An immutable reference was requested while a mutable reference is held.
+	AlreadyMutablyBorrowed,
+
+	/// A mutable reference was requested while another mutable or immutable reference is held.
+	AlreadyBorrowed,
+
+	/// A `SingletonUninit`, `SingletonBoxed` or `DeferredSingleton` was accessed before it was initialized.
+	Uninitialized,
+
+	/// A `SingletonUninit`, `SingletonBoxed` or `DeferredSingleton` was initialized more than once.
+	AlreadyInitialized,
+
+	/// A singleton was borrowed from a different thread than the one it belongs to, as identified by the provider set with `set_thread_id_provider`.
+	WrongThread,
+
+	/// A `SingletonLazy` was accessed from within its own initializer.
+	RecursiveInitialization,
+
+	/// A `SingletonLazy` or `SingletonLazySync` was accessed after its initializer panicked.
+	Poisoned
+}
+
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+/// A misuse of a singleton detected by its checks.
+///
+/// With the `structured-panic` feature enabled, checks panic with this as the payload (via `std::panic::panic_any`) so that panic hooks and `catch_unwind` callers can downcast it and inspect the violation programmatically.
+///
+/// With the `abort-on-violation` feature enabled, checks print its `Display` message to stderr and abort the process instead of panicking, so that no unwind can cross an FFI boundary.
+///
+/// Otherwise, checks panic with its `Display` message.
+pub struct BorrowViolation {
+	pub kind: ViolationKind,
+
+	/// The name of the singleton, if it was declared with `named_singleton!` or `Singleton::new_named`.
+	pub name: Option<&'static str>,
+
+	/// Where the conflicting borrow was acquired.
+	///
+	/// This is only recorded in debug builds with the `backtrace` feature, for checks which panic on a conflicting borrow.
+	#[cfg(feature = "backtrace")]
+	pub origin: Option<std::sync::Arc<std::backtrace::Backtrace>>
+}
+impl BorrowViolation {
+	#[inline]
+	pub(crate) fn new(kind: ViolationKind, name: Option<&'static str>) -> Self {
+		BorrowViolation {
+			kind,
+			name,
+			#[cfg(feature = "backtrace")]
+			origin: None
+		}
+	}
+}
+impl Display for BorrowViolation {
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		f.write_str(match self.kind {
+			ViolationKind::AlreadyMutablyBorrowed => "This singleton is already mutably borrowed",
+			ViolationKind::AlreadyBorrowed => "This singleton is already borrowed",
+			ViolationKind::Uninitialized => "This singleton has not been initialized yet",
+			ViolationKind::AlreadyInitialized => "This singleton has already been initialized",
+			ViolationKind::WrongThread => "This singleton was accessed from a different thread than the one it belongs to",
+			ViolationKind::RecursiveInitialization => "This singleton was accessed from within its own initializer (recursive lazy initialization)",
+			ViolationKind::Poisoned => "This singleton's initializer previously panicked"
+		})?;
+
+		if let Some(name) = self.name {
+			write!(f, " ({})", name)?;
+		}
+
+		#[cfg(feature = "backtrace")]
+		if let Some(origin) = &self.origin {
+			write!(f, "\n\nThe conflicting borrow was acquired at:\n{}", origin)?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub(crate) fn violation(kind: ViolationKind, name: Option<&'static str>) -> ! {
+	raise(BorrowViolation::new(kind, name))
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+#[cfg(all(feature = "backtrace", any(test, all(not(feature = "sync"), any(debug_assertions, feature = "spin")))))]
+pub(crate) fn violation_at(kind: ViolationKind, name: Option<&'static str>, origin: Option<std::sync::Arc<std::backtrace::Backtrace>>) -> ! {
+	raise(BorrowViolation { origin, ..BorrowViolation::new(kind, name) })
+}
+
+#[track_caller]
+fn raise(violation: BorrowViolation) -> ! {
+	#[cfg(feature = "abort-on-violation")] {
+		std::eprintln!("{} at {}", violation, core::panic::Location::caller());
+		std::process::abort()
+	}
+	#[cfg(all(feature = "structured-panic", not(feature = "abort-on-violation")))] {
+		std::panic::panic_any(violation)
+	}
+	#[cfg(not(any(feature = "structured-panic", feature = "abort-on-violation")))] {
+		panic!("{}", violation)
+	}
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+//! The heap-backed singletons only need `alloc`, so they must stay usable without `std`.
+#![cfg(all(feature = "alloc", not(feature = "std")))]
+
+use singlyton::{Singleton, SingletonBoxed, SingletonSlice, SingletonUninit, SnapshotHistory};
+use std::sync::Arc;
+
+#[test]
+fn test_alloc_without_std() {
+	static BOXED: SingletonBoxed<[u8; 4096]> = SingletonBoxed::new();
+	static SLICE: SingletonSlice<u32> = SingletonSlice::uninit();
+	static VEC: Singleton<Vec<u32>> = Singleton::new(Vec::new());
+	static ARC: SingletonUninit<Arc<str>> = SingletonUninit::uninit();
+	static COUNTER: Singleton<u32> = Singleton::new(0);
+
+	BOXED.init([1; 4096]);
+	debug_assert_eq!(BOXED.get().iter().map(|&byte| byte as usize).sum::<usize>(), 4096);
+
+	SLICE.init(vec![1, 2, 3]);
+	debug_assert_eq!(&*SLICE.get(), &[1, 2, 3]);
+
+	VEC.push(1);
+	VEC.push(2);
+	debug_assert_eq!(VEC.pop(), Some(2));
+	debug_assert_eq!(&*VEC.get(), &[1]);
+
+	ARC.init(Arc::from("hello"));
+	debug_assert_eq!(&*ARC.get_arc(), "hello");
+
+	let mut history = SnapshotHistory::new(2);
+	let start = history.capture(&COUNTER);
+	*COUNTER.get_mut() = 5;
+	history.capture(&COUNTER);
+	assert!(history.rewind(&COUNTER, start));
+	debug_assert_eq!(*COUNTER.get(), 0);
+}
+
+#[test]
+#[cfg(all(not(debug_assertions), not(any(feature = "observers", feature = "spin"))))]
+fn test_singleton_layout_without_observers() {
+	// Enabling `alloc` alone must not grow singletons, as embedded targets rely on their footprint
+	assert_eq!(core::mem::size_of::<Singleton<u32>>(), core::mem::size_of::<u32>());
+}
\ No newline at end of file
@@ -0,0 +1,89 @@
+//! Exercises the borrow checks of singletons under contention from many threads.
+//!
+//! In release builds without the `sync` or `spin` features there are no checks to exercise.
+#![cfg(any(debug_assertions, feature = "sync", feature = "spin"))]
+
+use singlyton::Singleton;
+use std::sync::{Arc, Barrier};
+
+const THREADS: usize = 16;
+const ITERATIONS: usize = 1000;
+
+#[test]
+#[cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+fn test_conflicting_borrows_panic_under_contention() {
+	static SINGLETON: Singleton<u64> = Singleton::new(0);
+
+	// Every thread tries to read the singleton while it is mutably borrowed
+	let guard = SINGLETON.get_mut();
+	let barrier = Arc::new(Barrier::new(THREADS));
+	let violations = (0..THREADS)
+		.map(|_| {
+			let barrier = barrier.clone();
+			std::thread::spawn(move || {
+				barrier.wait();
+				std::panic::catch_unwind(|| *SINGLETON.get()).is_err()
+			})
+		})
+		.collect::<Vec<_>>()
+		.into_iter()
+		.map(|handle| handle.join().unwrap())
+		.filter(|&violated| violated)
+		.count();
+	drop(guard);
+
+	assert_eq!(violations, THREADS);
+	assert_eq!(*SINGLETON.get(), 0);
+}
+
+#[test]
+fn test_no_lost_updates_under_contention() {
+	static SINGLETON: Singleton<u64> = Singleton::new(0);
+
+	// Every access that is not rejected by the checks must be observed, so nothing is lost to a data race
+	let barrier = Arc::new(Barrier::new(THREADS));
+	let successes = (0..THREADS)
+		.map(|_| {
+			let barrier = barrier.clone();
+			std::thread::spawn(move || {
+				barrier.wait();
+				(0..ITERATIONS).filter(|_| SINGLETON.try_with_mut(|value| *value += 1).is_ok()).count()
+			})
+		})
+		.collect::<Vec<_>>()
+		.into_iter()
+		.map(|handle| handle.join().unwrap())
+		.sum::<usize>();
+
+	assert!(successes > 0);
+	assert_eq!(*SINGLETON.get(), successes as u64);
+}
+
+#[test]
+#[cfg(any(feature = "sync", feature = "spin"))]
+fn test_upgrade_is_atomic_under_contention() {
+	static SINGLETON: Singleton<u64> = Singleton::new(0);
+
+	// Each thread inspects the value, then writes it back incremented; an upgrade which released the borrow in between would lose updates
+	let barrier = Arc::new(Barrier::new(THREADS));
+	let handles = (0..THREADS)
+		.map(|_| {
+			let barrier = barrier.clone();
+			std::thread::spawn(move || {
+				barrier.wait();
+				for _ in 0..ITERATIONS {
+					let read = SINGLETON.upgradable_read();
+					let seen = *read;
+					let mut write = singlyton::SinglytonUpgradableRef::upgrade(read);
+					assert_eq!(*write, seen);
+					*write = seen + 1;
+				}
+			})
+		})
+		.collect::<Vec<_>>();
+	for handle in handles {
+		handle.join().unwrap();
+	}
+
+	assert_eq!(*SINGLETON.get(), (THREADS * ITERATIONS) as u64);
+}
\ No newline at end of file
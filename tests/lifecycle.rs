@@ -0,0 +1,55 @@
+//! Deterministic teardown of interdependent singletons with a `DropGroup`.
+#![cfg(feature = "alloc")]
+
+use singlyton::{DropGroup, Singleton, SingletonUninit};
+
+static EVENTS: Singleton<Vec<&'static str>> = Singleton::new(Vec::new());
+
+struct Logger;
+impl Drop for Logger {
+	fn drop(&mut self) {
+		EVENTS.get_mut().push("logger");
+	}
+}
+
+// Logs through the logger when dropped, so it must be torn down first
+struct Database;
+impl Drop for Database {
+	fn drop(&mut self) {
+		let _logger = LOGGER.get();
+		EVENTS.get_mut().push("database");
+	}
+}
+
+// Closes its connections through the database when dropped
+struct Server;
+impl Drop for Server {
+	fn drop(&mut self) {
+		let _database = DATABASE.get();
+		let _logger = LOGGER.get();
+		EVENTS.get_mut().push("server");
+	}
+}
+
+static LOGGER: SingletonUninit<Logger> = SingletonUninit::uninit();
+static DATABASE: SingletonUninit<Database> = SingletonUninit::uninit();
+static SERVER: SingletonUninit<Server> = SingletonUninit::uninit();
+static GROUP: DropGroup = DropGroup::new();
+
+#[test]
+fn test_phased_shutdown() {
+	for _ in 0..2 {
+		GROUP.init(&LOGGER, Logger, 0);
+		GROUP.init(&DATABASE, Database, 1);
+		GROUP.init(&SERVER, Server, 2);
+		assert!(EVENTS.get().is_empty());
+
+		GROUP.shutdown();
+		assert_eq!(EVENTS.take(), ["server", "database", "logger"]);
+
+		// Every singleton has been dropped exactly once, and shutting down again does nothing
+		GROUP.shutdown();
+		assert!(EVENTS.get().is_empty());
+		assert!(LOGGER.try_get_opt().is_none() && DATABASE.try_get_opt().is_none() && SERVER.try_get_opt().is_none());
+	}
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+//! The thread id provider is global, so this runs in its own test binary.
+#![cfg(all(feature = "test-util", debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+
+use singlyton::Singleton;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Simulates a test harness running each test on a fresh thread
+static CURRENT_THREAD: AtomicUsize = AtomicUsize::new(0);
+
+#[test]
+fn test_reset_thread() {
+	static SINGLETON: Singleton<u32> = Singleton::new(0);
+
+	singlyton::set_thread_id_provider(|| CURRENT_THREAD.load(Ordering::Relaxed));
+
+	*SINGLETON.get_mut() += 1;
+
+	CURRENT_THREAD.store(1, Ordering::Relaxed);
+	assert!(std::panic::catch_unwind(|| *SINGLETON.get()).is_err());
+
+	// The next thread to borrow the singleton becomes its owner
+	SINGLETON.reset_thread();
+	*SINGLETON.get_mut() += 1;
+	assert_eq!(*SINGLETON.get(), 2);
+
+	CURRENT_THREAD.store(0, Ordering::Relaxed);
+	assert!(std::panic::catch_unwind(|| *SINGLETON.get()).is_err());
+}
\ No newline at end of file
@@ -0,0 +1,39 @@
+//! The thread id provider is global, so this runs in its own test binary.
+#![cfg(all(debug_assertions, not(any(feature = "sync", feature = "spin", feature = "abort-on-violation"))))]
+
+use singlyton::{BorrowViolation, Singleton, ViolationKind};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Simulates switching between threads
+static CURRENT_THREAD: AtomicUsize = AtomicUsize::new(0);
+
+fn violation_kind(payload: Box<dyn std::any::Any + Send>) -> Option<ViolationKind> {
+	match payload.downcast::<BorrowViolation>() {
+		Ok(violation) => Some(violation.kind),
+		Err(payload) => payload.downcast::<String>().ok().filter(|message| message.contains("different thread")).map(|_| ViolationKind::WrongThread)
+	}
+}
+
+#[test]
+fn test_thread_id_provider() {
+	static FIRST: Singleton<u32> = Singleton::new(0);
+	static SECOND: Singleton<u32> = Singleton::new(0);
+
+	singlyton::set_thread_id_provider(|| CURRENT_THREAD.load(Ordering::Relaxed));
+
+	*FIRST.get_mut() += 1;
+
+	CURRENT_THREAD.store(1, Ordering::Relaxed);
+	let payload = std::panic::catch_unwind(|| *FIRST.get()).unwrap_err();
+	assert_eq!(violation_kind(payload), Some(ViolationKind::WrongThread));
+	let payload = std::panic::catch_unwind(|| FIRST.try_with_mut(|_| ())).unwrap_err();
+	assert_eq!(violation_kind(payload), Some(ViolationKind::WrongThread));
+
+	// Singletons belong to the first thread that borrows them
+	*SECOND.get_mut() += 1;
+	assert_eq!(*SECOND.get(), 1);
+
+	CURRENT_THREAD.store(0, Ordering::Relaxed);
+	assert_eq!(*FIRST.get(), 1);
+	assert!(std::panic::catch_unwind(|| *SECOND.get()).is_err());
+}
\ No newline at end of file